@@ -1,5 +1,7 @@
 mod routes;
 mod server;
+mod session;
+mod timer;
 mod types;
 
 #[tokio::main]
@@ -1,69 +1,376 @@
-use axum::{extract::State, Json};
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use ed25519_dalek::SigningKey;
 use uuid::Uuid;
 
-use crate::types::{InitResponse, PlayRequest, PlayResponse};
+use crate::session::GameSession;
+use crate::types::{
+    HintsQuery, HintsResponse, HistoryResponse, InitRequest, InitResponse, PlayRequest,
+    PlayResponse, ReplayRequest, ReplayResponse, ResignRequest, TurnResponse,
+};
+use host::replay::{check_record, prove_record};
 use host::{players, Game};
-use ludo_core::{LudoGameState, Play};
+use ludo_bots::{rank_moves, win_probabilities, DEFAULT_ROLLOUTS};
+use ludo_core::{GameId, LudoGameState, Notation, Play, PlayJournal, Resign, RuleError, Salt};
+use rand::Rng;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::Mutex;
-
-pub type GameStore = Arc<Mutex<HashMap<String, Game>>>;
-
-pub async fn initialize_game(State(games): State<GameStore>) -> Json<InitResponse> {
-    let players = players::get_players();
-    let ludo_game_state = LudoGameState {
-        players,
-        current_player: 0,
-        dice_roll: 0,
-        winners: vec![],
-        sixes: 0,
-    };
+use std::time::Duration;
+use tokio::sync::{Mutex, Semaphore};
+
+// Every game has its own lock, so proving a move in one game leaves the store
+// and the other games free
+pub type GameStore = Arc<Mutex<HashMap<String, Arc<Mutex<GameSession>>>>>;
+
+const DEFAULT_MAX_TIMEOUTS: u8 = 3;
+
+// Longest game log `/replay` proves. Every action is a separate proof, so this
+// bounds how long one request can keep a proving thread busy
+const MAX_REPLAY_MOVES: usize = 400;
+
+// Replays proven at once; further requests are turned away rather than queued
+// onto the blocking pool
+const MAX_CONCURRENT_REPLAYS: usize = 2;
+static REPLAY_PERMITS: Semaphore = Semaphore::const_new(MAX_CONCURRENT_REPLAYS);
+
+pub async fn initialize_game(
+    State(games): State<GameStore>,
+    init_request: Option<Json<InitRequest>>,
+) -> Result<Json<InitResponse>, (StatusCode, String)> {
+    // An empty body starts a classic four-player game played to the end
+    let init_request = init_request
+        .map(|Json(request)| request)
+        .unwrap_or_default();
+    if init_request.ranked && init_request.hints == Some(true) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Ranked games cannot serve hints".into(),
+        ));
+    }
+    let board = init_request.board;
+    let mut players = players::get_players(board);
+    for (player, public_key) in players.iter_mut().zip(init_request.public_keys.iter()) {
+        player.public_key = *public_key;
+    }
+    // Each game gets its own arbiter key, which lets the turn timer forfeit
+    // players who sign their moves
+    let arbiter = SigningKey::from_bytes(&rand::thread_rng().gen());
+    let uuid = Uuid::new_v4();
+    let ludo_game_state = LudoGameState::new(board, players)
+        .with_game_id(GameId(*uuid.as_bytes()))
+        .with_end_condition(init_request.end_condition)
+        .with_arbiter(arbiter.verifying_key().to_bytes());
 
-    let game_instance = Game::new(ludo_game_state);
+    let salt = init_request
+        .salted
+        .then(|| rand::thread_rng().gen::<Salt>());
+    let mut game_instance = Game::new(ludo_game_state);
+    if let Some(salt) = salt {
+        game_instance = game_instance.with_salt(salt);
+    }
     let init_message = game_instance.init().expect("Failed to initialize game");
     let commit = init_message
         .verify_and_get_commit()
         .expect("Failed to verify init commit");
 
-    let game_id = Uuid::new_v4().to_string();
-    games.lock().await.insert(game_id.clone(), game_instance);
+    let session = GameSession::new(
+        game_instance,
+        init_request.turn_timeout_secs.map(Duration::from_secs),
+        init_request.max_timeouts.unwrap_or(DEFAULT_MAX_TIMEOUTS),
+    )
+    .with_hints(init_request.hints.unwrap_or(!init_request.ranked))
+    .with_arbiter(arbiter);
+
+    let game_id = uuid.to_string();
+    games
+        .lock()
+        .await
+        .insert(game_id.clone(), Arc::new(Mutex::new(session)));
 
-    Json(InitResponse { game_id, commit })
+    Ok(Json(InitResponse {
+        game_id,
+        commit,
+        salt,
+    }))
 }
 
 pub async fn play_game(
     State(games): State<GameStore>,
     Json(play_request): Json<PlayRequest>,
-) -> Json<PlayResponse> {
-    let mut games = games.lock().await;
+) -> Result<Json<PlayResponse>, (StatusCode, String)> {
+    let session = find_session(&games, &play_request.game_id)
+        .await
+        .ok_or(game_not_found())?;
+    let mut session = session.lock().await;
 
-    let game_instance = games
-        .get_mut(&play_request.game_id)
-        .expect("Game not found");
+    // Refuse the move before proving if the client is looking at another state,
+    // telling it which state the game is in
+    let digest = session.game.state.digest().to_string();
+    if play_request
+        .state_hash
+        .as_ref()
+        .is_some_and(|state_hash| *state_hash != digest)
+    {
+        return Err((
+            StatusCode::CONFLICT,
+            format!("Client state does not match the game state {}", digest),
+        ));
+    }
 
     let play = Play {
         current_player: play_request.current_player,
         dice_roll: play_request.dice_roll,
         piece_index: play_request.piece_index,
+        signature: play_request.signature,
     };
 
-    let play_message = game_instance.play(&play).expect("Failed to make play");
+    let player = session.game.state.current_player;
+    let play_message = session.game.play(&play).map_err(action_error)?;
     let commit = play_message
-        .verify_and_get_commit()
+        .verify_and_get_journal()
         .expect("Failed to verify play commit");
 
-    // Check for winners after play
-    let game_ended = game_instance.state.winners.len() >= 3;
-    if game_ended {
-        game_instance
-            .verify_winners()
-            .expect("Failed to verify winners");
-    }
+    Ok(Json(play_response(&mut session, player, commit)))
+}
 
-    Json(PlayResponse {
+pub async fn resign_game(
+    State(games): State<GameStore>,
+    Json(resign_request): Json<ResignRequest>,
+) -> Result<Json<PlayResponse>, (StatusCode, String)> {
+    let session = find_session(&games, &resign_request.game_id)
+        .await
+        .ok_or(game_not_found())?;
+    let mut session = session.lock().await;
+
+    let resign = Resign {
+        player: resign_request.player,
+        signature: resign_request.signature,
+    };
+    let play_message = session.game.resign(&resign).map_err(action_error)?;
+    let commit = play_message
+        .verify_and_get_journal()
+        .expect("Failed to verify resign commit");
+
+    Ok(Json(play_response(
+        &mut session,
+        resign_request.player,
         commit,
-        state: game_instance.state.clone(),
-        game_ended,
+    )))
+}
+
+pub async fn get_turn(
+    State(games): State<GameStore>,
+    Path(game_id): Path<String>,
+) -> Json<TurnResponse> {
+    let session = find_session(&games, &game_id)
+        .await
+        .expect("Game not found");
+    let session = session.lock().await;
+
+    Json(TurnResponse {
+        current_player: session.game.state.current_player,
+        turn_deadline: session.deadline_ms(),
+        timeouts: session.clock.timeouts.clone(),
+        game_ended: session.game.state.is_over(),
+        move_number: session.game.state.move_number + 1,
+        state_hash: session.game.state.digest().to_string(),
+    })
+}
+
+pub async fn get_history(
+    State(games): State<GameStore>,
+    Path(game_id): Path<String>,
+) -> Json<HistoryResponse> {
+    let session = find_session(&games, &game_id)
+        .await
+        .expect("Game not found");
+    let session = session.lock().await;
+
+    Json(HistoryResponse {
+        history: session.history.clone(),
     })
 }
+
+pub async fn get_hints(
+    State(games): State<GameStore>,
+    Path(game_id): Path<String>,
+    Query(query): Query<HintsQuery>,
+) -> Result<Json<HintsResponse>, (StatusCode, String)> {
+    if query
+        .dice_roll
+        .is_some_and(|dice_roll| !(1..=6).contains(&dice_roll))
+    {
+        return Err((StatusCode::BAD_REQUEST, "Dice roll must be 1 to 6".into()));
+    }
+    // Rollouts take a while, so they run on a copy without holding the game
+    let state = {
+        let session = find_session(&games, &game_id)
+            .await
+            .ok_or(game_not_found())?;
+        let session = session.lock().await;
+        if !session.hints {
+            return Err((
+                StatusCode::FORBIDDEN,
+                "Hints are disabled for this game".into(),
+            ));
+        }
+        session.game.state.clone()
+    };
+
+    let response = tokio::task::spawn_blocking(move || {
+        // Seeded by position, so asking again gives the same answer
+        let seed = state.move_number as u64;
+        HintsResponse {
+            move_number: state.move_number + 1,
+            win_probabilities: win_probabilities(&state, DEFAULT_ROLLOUTS, seed),
+            moves: query
+                .dice_roll
+                .map(|dice_roll| rank_moves(&state, dice_roll, DEFAULT_ROLLOUTS, seed))
+                .unwrap_or_default(),
+        }
+    })
+    .await
+    .expect("Failed to compute hints");
+
+    Ok(Json(response))
+}
+
+pub async fn replay_game(
+    Json(replay_request): Json<ReplayRequest>,
+) -> Result<Json<ReplayResponse>, (StatusCode, String)> {
+    let bad_request = |message: String| (StatusCode::BAD_REQUEST, message);
+    let record = match (replay_request.record, replay_request.notation) {
+        (Some(record), None) => record,
+        (None, Some(notation)) => Notation::parse(&notation)
+            .map_err(|e| bad_request(format!("Invalid notation: {}", e)))?
+            .to_record()
+            .map_err(|e| bad_request(e.to_string()))?,
+        _ => return Err(bad_request("Expected either a record or notation".into())),
+    };
+    if record.moves.len() > MAX_REPLAY_MOVES {
+        return Err(bad_request(format!(
+            "Replays are limited to {} moves, this log has {}",
+            MAX_REPLAY_MOVES,
+            record.moves.len()
+        )));
+    }
+    // Refuse logs that break the rules before spending any time proving them
+    check_record(&record).map_err(|e| bad_request(e.to_string()))?;
+
+    let permit = REPLAY_PERMITS.try_acquire().map_err(|_| {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Too many replays are being proven, try again later".to_string(),
+        )
+    })?;
+    // Proving a whole game takes a while; it runs apart from the other games
+    let response = tokio::task::spawn_blocking(move || -> anyhow::Result<ReplayResponse> {
+        let _permit = permit;
+        let replay = prove_record(&record, replay_request.salt)?;
+        Ok(ReplayResponse {
+            commit: replay.init.verify_and_get_commit()?,
+            journals: replay
+                .plays
+                .iter()
+                .map(|play| play.verify_and_get_journal())
+                .collect::<Result<_, _>>()?,
+            placements: replay
+                .winners
+                .as_ref()
+                .map(|winners| winners.verify_and_get_commit().map(|c| c.placements))
+                .transpose()?,
+            receipts: replay.receipts(),
+        })
+    })
+    .await
+    .expect("Failed to prove game")
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(response))
+}
+
+async fn find_session(games: &GameStore, game_id: &str) -> Option<Arc<Mutex<GameSession>>> {
+    games.lock().await.get(game_id).cloned()
+}
+
+fn game_not_found() -> (StatusCode, String) {
+    (StatusCode::NOT_FOUND, "Game not found".into())
+}
+
+// Actions the rules refuse are the client's fault: 403 for a missing or wrong
+// signature, 400 otherwise. Anything else went wrong while proving.
+fn action_error(error: anyhow::Error) -> (StatusCode, String) {
+    let status = match error.downcast_ref::<RuleError>() {
+        Some(RuleError::Unauthorized) => StatusCode::FORBIDDEN,
+        Some(_) => StatusCode::BAD_REQUEST,
+        None => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status, error.to_string())
+}
+
+// Records a proven action and builds the response, proving the winners once the game ends
+fn play_response(session: &mut GameSession, player: usize, commit: PlayJournal) -> PlayResponse {
+    session
+        .record(player, commit.clone(), false)
+        .expect("Failed to verify winners");
+
+    let events = session
+        .history
+        .last()
+        .map(|entry| entry.events.clone())
+        .unwrap_or_default();
+
+    PlayResponse {
+        commit,
+        events,
+        state: session.game.state.clone(),
+        game_ended: session.game.state.is_over(),
+        placements: session.placements.clone(),
+        turn_deadline: session.deadline_ms(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ludo_core::BoardLayout;
+
+    #[tokio::test]
+    async fn unsigned_resign_by_a_keyed_player_is_forbidden() {
+        let board = BoardLayout::Classic;
+        let mut players = players::get_players(board);
+        players[1].public_key = Some([1; 32]);
+        let game = Game::new(LudoGameState::new(board, players));
+        let games = GameStore::default();
+        games.lock().await.insert(
+            "game".into(),
+            Arc::new(Mutex::new(GameSession::new(game, None, 3))),
+        );
+
+        let resign = |player| {
+            Json(ResignRequest {
+                game_id: "game".into(),
+                player,
+                signature: None,
+            })
+        };
+        let status = |result: Result<Json<PlayResponse>, (StatusCode, String)>| {
+            result.err().map(|(status, _)| status)
+        };
+        assert_eq!(
+            status(resign_game(State(games.clone()), resign(1)).await),
+            Some(StatusCode::FORBIDDEN)
+        );
+        assert_eq!(
+            status(resign_game(State(games.clone()), resign(7)).await),
+            Some(StatusCode::BAD_REQUEST)
+        );
+        assert_eq!(
+            status(resign_game(State(GameStore::default()), resign(1)).await),
+            Some(StatusCode::NOT_FOUND)
+        );
+    }
+}
@@ -6,7 +6,11 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-use crate::routes::handlers::{initialize_game, play_game, GameStore};
+use crate::routes::handlers::{
+    get_hints, get_history, get_turn, initialize_game, play_game, replay_game, resign_game,
+    GameStore,
+};
+use crate::timer::run_turn_timer;
 
 pub async fn run_server() {
     // Initialize tracing
@@ -21,7 +25,15 @@ pub async fn run_server() {
     let app = Router::new()
         .route("/init", post(initialize_game))
         .route("/play", post(play_game))
-        .with_state(games);
+        .route("/resign", post(resign_game))
+        .route("/replay", post(replay_game))
+        .route("/games/:id/turn", get(get_turn))
+        .route("/games/:id/history", get(get_history))
+        .route("/games/:id/hints", get(get_hints))
+        .with_state(games.clone());
+
+    // Act for players whose turn deadline has passed
+    tokio::spawn(run_turn_timer(games));
 
     // Run server
     let listener = tokio::net::TcpListener::bind("127.0.0.1:3000")
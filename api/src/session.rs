@@ -0,0 +1,213 @@
+use anyhow::Result;
+use ed25519_dalek::SigningKey;
+use host::Game;
+use ludo_core::{GameEvent, Placement, Play, PlayJournal, Resign};
+use rand::Rng;
+use serde::Serialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Serialize)]
+pub struct HistoryEntry {
+    pub move_number: usize,
+    pub player: usize,
+    pub commit: PlayJournal,
+    pub events: Vec<GameEvent>,
+    pub timed_out: bool, // Played or forfeited by the server when the turn expired
+}
+
+// Per-turn deadline kept on the server, so it keeps running while a client
+// is disconnected and can be read back when it reconnects
+pub struct TurnClock {
+    pub turn_timeout: Option<Duration>,
+    pub max_timeouts: u8, // Consecutive timeouts before a player forfeits
+    pub deadline: Option<SystemTime>,
+    pub timeouts: Vec<u8>, // Consecutive timeouts per player
+}
+
+pub struct GameSession {
+    pub game: Game,
+    pub history: Vec<HistoryEntry>,
+    pub clock: TurnClock,
+    pub placements: Option<Vec<Placement>>, // Proven final ranking once the game ends
+    pub hints: bool,                        // Whether players may ask for move hints
+    pub arbiter: Option<SigningKey>,        // The state's arbiter key, to forfeit keyed players
+}
+
+impl GameSession {
+    pub fn new(game: Game, turn_timeout: Option<Duration>, max_timeouts: u8) -> Self {
+        let players = game.state.players.len();
+        let mut session = Self {
+            game,
+            history: vec![],
+            clock: TurnClock {
+                turn_timeout,
+                max_timeouts: max_timeouts.max(1),
+                deadline: None,
+                timeouts: vec![0; players],
+            },
+            placements: None,
+            hints: true,
+            arbiter: None,
+        };
+        session.restart_clock();
+        session
+    }
+
+    pub fn with_hints(mut self, hints: bool) -> Self {
+        self.hints = hints;
+        self
+    }
+
+    // `arbiter` must be the key the game state names as its arbiter
+    pub fn with_arbiter(mut self, arbiter: SigningKey) -> Self {
+        self.arbiter = Some(arbiter);
+        self
+    }
+
+    // Starts the deadline for whoever is to move next
+    fn restart_clock(&mut self) {
+        self.clock.deadline = match self.game.state.is_over() {
+            true => None,
+            false => self
+                .clock
+                .turn_timeout
+                .map(|timeout| SystemTime::now() + timeout),
+        };
+    }
+
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        self.clock.deadline.is_some_and(|deadline| now >= deadline)
+    }
+
+    // Deadline for the current turn in milliseconds since the Unix epoch
+    pub fn deadline_ms(&self) -> Option<u64> {
+        self.clock.deadline.map(|deadline| {
+            deadline
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64
+        })
+    }
+
+    // Appends a proven action to the history, restarts the turn clock and
+    // proves the final ranking once the game has ended
+    pub fn record(&mut self, player: usize, commit: PlayJournal, timed_out: bool) -> Result<()> {
+        if !timed_out {
+            self.clock.timeouts[player] = 0;
+        }
+        let events = self
+            .game
+            .record
+            .moves
+            .last()
+            .map(|m| m.events.clone())
+            .unwrap_or_default();
+        self.history.push(HistoryEntry {
+            move_number: self.history.len() + 1,
+            player,
+            commit,
+            events,
+            timed_out,
+        });
+        self.restart_clock();
+
+        if self.game.state.is_over() && self.placements.is_none() {
+            let winners_commit = self.game.verify_winners()?.verify_and_get_commit()?;
+            self.placements = Some(winners_commit.placements);
+        }
+        Ok(())
+    }
+
+    // Resignation the server may play for `player` once they run out of time:
+    // unsigned for players without a key, signed as arbiter for the others
+    pub fn forfeit(&self, player: usize) -> Option<Resign> {
+        let resign = Resign {
+            player,
+            signature: None,
+        };
+        match (&self.game.state.players[player].public_key, &self.arbiter) {
+            (None, _) => Some(resign),
+            (Some(_), Some(arbiter)) => Some(resign.signed(arbiter, &self.game.state)),
+            (Some(_), None) => None,
+        }
+    }
+
+    // Acts for a player whose turn expired: plays the first legal piece for a
+    // server-rolled die (or passes), or forfeits the player after too many timeouts.
+    // The server cannot roll for players with a key, not even a roll that only
+    // passes, so for them it only counts the timeout and restarts their clock
+    // until they are forfeited as arbiter. A game started without an arbiter
+    // cannot forfeit them, and stops their clock instead.
+    pub fn play_timeout(&mut self) -> Result<Option<PlayJournal>> {
+        let player = self.game.state.current_player;
+        let timeouts = self.clock.timeouts[player] + 1;
+        let dice_roll = rand::thread_rng().gen_range(1..=6);
+        let legal_moves = self.game.state.legal_moves(dice_roll);
+        let signs_moves = self.game.state.players[player].public_key.is_some();
+
+        let forfeits = timeouts >= self.clock.max_timeouts;
+        if signs_moves && !forfeits {
+            self.clock.timeouts[player] = timeouts;
+            self.restart_clock();
+            return Ok(None);
+        }
+
+        let message = match forfeits {
+            true => match self.forfeit(player) {
+                Some(resign) => self.game.resign(&resign)?,
+                None => {
+                    self.clock.timeouts[player] = timeouts;
+                    self.clock.deadline = None;
+                    return Ok(None);
+                }
+            },
+            false => self.game.play(&Play {
+                current_player: player,
+                dice_roll,
+                piece_index: legal_moves.first().copied().unwrap_or(0),
+                signature: None,
+            })?,
+        };
+
+        let commit = message.verify_and_get_journal()?;
+        self.clock.timeouts[player] = timeouts;
+        self.record(player, commit.clone(), true)?;
+        Ok(Some(commit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use host::players;
+    use ludo_core::{BoardLayout, LudoGameState, PlayGameParams};
+
+    #[test]
+    fn keyed_players_are_forfeited_only_by_the_arbiter() {
+        let arbiter = SigningKey::from_bytes(&[9; 32]);
+        let board = BoardLayout::Classic;
+        let mut players = players::get_players(board);
+        players[0].public_key = Some([1; 32]);
+        let state = LudoGameState::new(board, players);
+        let refereed = state
+            .clone()
+            .with_arbiter(arbiter.verifying_key().to_bytes());
+
+        // Without an arbiter the keyed player's clock stops at the last timeout
+        let mut session = GameSession::new(Game::new(state), Some(Duration::from_secs(1)), 2);
+        assert_eq!(session.forfeit(0), None);
+        assert!(session.play_timeout().unwrap().is_none());
+        assert_eq!(session.clock.timeouts[0], 1);
+        assert!(session.clock.deadline.is_some());
+        assert!(session.play_timeout().unwrap().is_none());
+        assert_eq!(session.clock.timeouts[0], 2);
+        assert_eq!(session.clock.deadline, None);
+
+        // With one, the forfeit carries a signature the rules accept
+        let session = GameSession::new(Game::new(refereed.clone()), None, 2).with_arbiter(arbiter);
+        let forfeit = session.forfeit(0).unwrap();
+        assert!(forfeit.signature.is_some());
+        assert_eq!(PlayGameParams::new(refereed, forfeit).validate(), Ok(()));
+        assert!(session.forfeit(1).unwrap().signature.is_none());
+    }
+}
@@ -0,0 +1,38 @@
+use std::time::{Duration, SystemTime};
+
+use crate::routes::handlers::GameStore;
+
+// Checks every game once a second and acts for players whose turn expired.
+// The store is only held to list the games, and each move is proven on a
+// blocking thread that holds just its own game.
+pub async fn run_turn_timer(games: GameStore) {
+    let mut interval = tokio::time::interval(Duration::from_secs(1));
+    loop {
+        interval.tick().await;
+
+        let sessions: Vec<_> = games
+            .lock()
+            .await
+            .iter()
+            .map(|(game_id, session)| (game_id.clone(), session.clone()))
+            .collect();
+        let now = SystemTime::now();
+        for (game_id, session) in sessions {
+            // A game that is busy proving a move gets another look next tick
+            let Ok(mut session) = session.try_lock_owned() else {
+                continue;
+            };
+            if !session.is_expired(now) {
+                continue;
+            }
+            tokio::task::spawn_blocking(move || {
+                if let Err(e) = session.play_timeout() {
+                    // Stop the clock rather than retry every second; the
+                    // players can still move themselves
+                    session.clock.deadline = None;
+                    tracing::error!(game_id, "Failed to act on an expired turn: {:?}", e);
+                }
+            });
+        }
+    }
+}
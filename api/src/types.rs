@@ -1,17 +1,45 @@
-use ludo_core::{InitializeGameStateCommit, LudoGameState, PlayGameCommit};
+use ludo_bots::MoveHint;
+use ludo_core::{
+    BoardLayout, EndCondition, GameEvent, GameRecord, InitializeGameStateCommit, LudoGameState,
+    Placement, PlayJournal, PublicKey, Salt,
+};
+use risc0_zkvm::Receipt;
 use serde::{Deserialize, Serialize};
 
+use crate::session::HistoryEntry;
+
+#[derive(Default, Deserialize)]
+pub struct InitRequest {
+    #[serde(default)]
+    pub board: BoardLayout,
+    #[serde(default)]
+    pub end_condition: EndCondition,
+    pub turn_timeout_secs: Option<u64>, // No deadline when omitted
+    pub max_timeouts: Option<u8>,       // Consecutive timeouts before forfeiting
+    #[serde(default)]
+    pub salted: bool, // Hide states behind salted commitments
+    #[serde(default)]
+    pub public_keys: Vec<Option<PublicKey>>, // Per seat; seats with a key must sign their moves
+    #[serde(default)]
+    pub ranked: bool,
+    pub hints: Option<bool>, // Serve move hints; off by default for ranked games
+}
+
 #[derive(Serialize)]
 pub struct InitResponse {
     pub game_id: String,
     pub commit: InitializeGameStateCommit,
+    pub salt: Option<Salt>, // Only for the players; needed to open commitments in a dispute
 }
 
 #[derive(Serialize)]
 pub struct PlayResponse {
-    pub commit: PlayGameCommit,
+    pub commit: PlayJournal,
+    pub events: Vec<GameEvent>, // What the action did, for animating it
     pub state: LudoGameState,
     pub game_ended: bool,
+    pub placements: Option<Vec<Placement>>, // Proven final ranking once the game ends
+    pub turn_deadline: Option<u64>,         // Unix time in milliseconds
 }
 
 #[derive(Deserialize)]
@@ -20,4 +48,56 @@ pub struct PlayRequest {
     pub current_player: usize,
     pub dice_roll: u8,
     pub piece_index: u8,
+    pub signature: Option<Vec<u8>>, // Required from players that registered a key
+    pub state_hash: Option<String>, // Hex digest the client expects the move to be played on
+}
+
+#[derive(Deserialize)]
+pub struct ResignRequest {
+    pub game_id: String,
+    pub player: usize,
+    pub signature: Option<Vec<u8>>, // Required from players that registered a key
+}
+
+#[derive(Serialize)]
+pub struct TurnResponse {
+    pub current_player: usize,
+    pub turn_deadline: Option<u64>, // Unix time in milliseconds
+    pub timeouts: Vec<u8>,          // Consecutive timeouts per player
+    pub game_ended: bool,
+    pub move_number: u32,   // Number of the next move, as signed by the player
+    pub state_hash: String, // Hex digest of the state the next move is played on
+}
+
+#[derive(Serialize)]
+pub struct HistoryResponse {
+    pub history: Vec<HistoryEntry>,
+}
+
+#[derive(Deserialize)]
+pub struct HintsQuery {
+    pub dice_roll: Option<u8>, // Rank the moves for this roll when given
+}
+
+#[derive(Serialize)]
+pub struct HintsResponse {
+    pub move_number: u32,            // Number of the next move
+    pub win_probabilities: Vec<f64>, // Chance of each player finishing first
+    pub moves: Vec<MoveHint>,        // Legal moves for the roll, best first
+}
+
+// A finished or abandoned game to prove again, given either as a record or as notation
+#[derive(Deserialize)]
+pub struct ReplayRequest {
+    pub record: Option<GameRecord>,
+    pub notation: Option<String>,
+    pub salt: Option<Salt>, // The game's salt, to reproduce the commitments of a hidden game
+}
+
+#[derive(Serialize)]
+pub struct ReplayResponse {
+    pub commit: InitializeGameStateCommit,
+    pub journals: Vec<PlayJournal>,         // One per action
+    pub placements: Option<Vec<Placement>>, // Proven final ranking if the game ended
+    pub receipts: Vec<Receipt>,             // Init, every action, then the winners
 }
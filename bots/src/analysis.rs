@@ -0,0 +1,86 @@
+use ludo_core::{LudoGameState, PieceRef, PieceStatus};
+
+// Where a legal move would take a piece and what it would do there
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Move {
+    pub piece: u8,
+    pub from: i8, // Path position before the move, -1 at home
+    pub to: i8,   // Path position after the move
+    pub captures: Option<PieceRef>,
+    pub finishes: bool,
+}
+
+// Every legal move of the player to move with `dice_roll`
+pub fn moves(state: &LudoGameState, dice_roll: u8) -> Vec<Move> {
+    let player = state.current_player;
+    let path = state.path(player);
+    let last_position = state.board.last_position();
+    state
+        .legal_moves(dice_roll)
+        .into_iter()
+        .map(|piece| {
+            let from = state.players[player].pieces[piece as usize].position;
+            let to = if from < 0 { 0 } else { from + dice_roll as i8 };
+            let finishes = to as usize == last_position;
+            Move {
+                piece,
+                from,
+                to,
+                captures: if finishes {
+                    None
+                } else {
+                    occupant(state, path[to as usize])
+                },
+                finishes,
+            }
+        })
+        .collect()
+}
+
+// The first opponent piece on `cell`, which is the one a move there captures
+pub fn occupant(state: &LudoGameState, cell: u8) -> Option<PieceRef> {
+    let mover = state.current_player;
+    state
+        .players
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != mover)
+        .find_map(|(i, player)| {
+            let path = state.path(i);
+            player
+                .pieces
+                .iter()
+                .position(|p| p.status == PieceStatus::Active && path[p.position as usize] == cell)
+                .map(|j| PieceRef {
+                    player: i,
+                    piece: j as u8,
+                })
+        })
+}
+
+// How many opponent pieces could land on `cell` with their next roll: active
+// pieces up to six steps behind it, and home pieces if it is their start cell
+pub fn threats(state: &LudoGameState, player: usize, cell: u8) -> usize {
+    let last_position = state.board.last_position();
+    state
+        .players
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != player && state.is_playing(i))
+        .map(|(i, opponent)| {
+            let path = state.path(i);
+            opponent
+                .pieces
+                .iter()
+                .filter(|p| match p.status {
+                    PieceStatus::Home => path[0] == cell,
+                    PieceStatus::Active => (1..=6).any(|d| {
+                        let to = p.position as usize + d;
+                        to < last_position && path[to] == cell
+                    }),
+                    PieceStatus::Win => false,
+                })
+                .count()
+        })
+        .sum()
+}
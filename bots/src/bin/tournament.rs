@@ -0,0 +1,61 @@
+use clap::Parser;
+use ludo_bots::tournament::Tournament;
+use ludo_bots::BotKind;
+use ludo_core::{BoardLayout, EndCondition};
+use std::thread;
+
+/// Plays many fast, unproven games between bots and reports win rates by seat
+/// and color, game length, captures and how often the three-sixes rule fires
+#[derive(Parser)]
+struct Args {
+    /// Number of games to play
+    #[arg(long, default_value_t = 1_000_000)]
+    games: u64,
+
+    /// One bot per seat, in turn order, e.g. random,race,defensive,search-easy
+    #[arg(long, value_delimiter = ',', default_values = ["random", "random", "random", "random"])]
+    bots: Vec<BotKind>,
+
+    /// classic or six-player
+    #[arg(long, default_value = "classic")]
+    board: BoardLayout,
+
+    /// first, last, or top:N
+    #[arg(long, default_value = "first")]
+    end_condition: EndCondition,
+
+    /// Deal colors to seats at random each game
+    #[arg(long)]
+    shuffle_colors: bool,
+
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Defaults to one per core
+    #[arg(long)]
+    threads: Option<usize>,
+}
+
+fn main() {
+    let args = Args::parse();
+    if args.bots.len() < 2 || args.bots.len() > args.board.max_players() {
+        eprintln!(
+            "A {:?} board seats 2 to {} bots",
+            args.board,
+            args.board.max_players()
+        );
+        std::process::exit(2);
+    }
+
+    let tournament = Tournament {
+        board: args.board,
+        bots: args.bots,
+        end_condition: args.end_condition,
+        shuffle_colors: args.shuffle_colors,
+        seed: args.seed,
+    };
+    let threads = args
+        .threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+    print!("{}", tournament.run(args.games, threads));
+}
@@ -0,0 +1,144 @@
+use crate::analysis::{moves, threats, Move};
+use crate::Strategy;
+use ludo_core::LudoGameState;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::cmp::Reverse;
+
+// Picks uniformly among the legal moves
+pub struct RandomBot {
+    rng: StdRng,
+}
+
+impl RandomBot {
+    pub fn new(seed: u64) -> Self {
+        RandomBot {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for RandomBot {
+    fn choose(&mut self, state: &LudoGameState, dice_roll: u8) -> u8 {
+        *state.legal_moves(dice_roll).choose(&mut self.rng).unwrap()
+    }
+}
+
+// Always moves the lowest-numbered piece that can move
+pub struct FirstLegal;
+
+impl Strategy for FirstLegal {
+    fn choose(&mut self, state: &LudoGameState, dice_roll: u8) -> u8 {
+        state.legal_moves(dice_roll)[0]
+    }
+}
+
+// Pushes the piece furthest along, so pieces finish one at a time
+pub struct Race;
+
+impl Strategy for Race {
+    fn choose(&mut self, state: &LudoGameState, dice_roll: u8) -> u8 {
+        furthest(&moves(state, dice_roll)).piece
+    }
+}
+
+// Captures whenever it can, taking the victim closest to finishing, and races
+// otherwise
+pub struct GreedyCapture;
+
+impl Strategy for GreedyCapture {
+    fn choose(&mut self, state: &LudoGameState, dice_roll: u8) -> u8 {
+        let moves = moves(state, dice_roll);
+        moves
+            .iter()
+            .filter_map(|m| m.captures.map(|victim| (m, victim)))
+            .max_by_key(|(_, victim)| {
+                state.players[victim.player].pieces[victim.piece as usize].position
+            })
+            .map(|(m, _)| m.piece)
+            .unwrap_or_else(|| furthest(&moves).piece)
+    }
+}
+
+// Avoids landing within six steps of an opponent. Among equally safe moves it
+// prefers rescuing a threatened piece, then the piece furthest along.
+pub struct Defensive;
+
+impl Strategy for Defensive {
+    fn choose(&mut self, state: &LudoGameState, dice_roll: u8) -> u8 {
+        let player = state.current_player;
+        let path = state.path(player);
+        let danger = |position: i8| -> usize {
+            if position < 0 || position as usize == state.board.last_position() {
+                0
+            } else {
+                threats(state, player, path[position as usize])
+            }
+        };
+        moves(state, dice_roll)
+            .iter()
+            .min_by_key(|m| (danger(m.to), Reverse(danger(m.from)), Reverse(m.to)))
+            .unwrap()
+            .piece
+    }
+}
+
+// The move that leaves its piece furthest along, finishing moves first
+fn furthest(moves: &[Move]) -> &Move {
+    moves.iter().max_by_key(|m| m.to).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ludo_core::{BoardLayout, Piece, PieceStatus, Player};
+
+    fn state() -> LudoGameState {
+        let players = BoardLayout::Classic
+            .colors()
+            .iter()
+            .map(|c| {
+                Player::new(
+                    format!("{:?}", c),
+                    *c,
+                    [0; 4].map(|_| Piece::new(-1, PieceStatus::Home)),
+                )
+            })
+            .collect();
+        LudoGameState::new(BoardLayout::Classic, players)
+    }
+
+    // Red position of the cell Green reaches at `position`
+    fn red_position(state: &LudoGameState, green_position: usize) -> i8 {
+        let cell = state.path(1)[green_position];
+        state.path(0).iter().position(|&c| c == cell).unwrap() as i8
+    }
+
+    #[test]
+    fn heuristics_pick_their_preferred_piece() {
+        let mut state = state();
+        // Green sits at its position 20, where Red piece 1 lands with a 3
+        let target = red_position(&state, 20);
+        state.players[1].pieces[0] = Piece::new(20, PieceStatus::Active);
+        state.players[0].pieces[0] = Piece::new(target + 10, PieceStatus::Active);
+        state.players[0].pieces[1] = Piece::new(target - 3, PieceStatus::Active);
+        state.players[0].pieces[2] = Piece::new(target - 1, PieceStatus::Active);
+
+        assert_eq!(FirstLegal.choose(&state, 3), 0);
+        assert_eq!(Race.choose(&state, 3), 0);
+        assert_eq!(GreedyCapture.choose(&state, 3), 1);
+        let mut random = RandomBot::new(7);
+        for _ in 0..20 {
+            assert!(state.legal_moves(3).contains(&random.choose(&state, 3)));
+        }
+
+        // With a 5, piece 1 can escape Green while the others are not threatened.
+        // Blue and Yellow leave so their start cells are no danger.
+        state.eliminated = vec![2, 3];
+        state.players[0].pieces[1] = Piece::new(target + 2, PieceStatus::Active);
+        state.players[0].pieces[2] = Piece::new(target - 8, PieceStatus::Active);
+        assert_eq!(Race.choose(&state, 5), 0);
+        assert_eq!(Defensive.choose(&state, 5), 1);
+    }
+}
@@ -0,0 +1,116 @@
+use crate::search::{simulation_state, successors};
+use crate::{play, GreedyCapture};
+use ludo_core::{LudoGameState, PlayGameParams};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+
+pub const DEFAULT_ROLLOUTS: usize = 500;
+
+// Rollouts still running after this many actions count as nobody's win
+const MAX_ROLLOUT_MOVES: u32 = 10_000;
+
+// A legal move and the mover's chance of finishing first after it
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct MoveHint {
+    pub piece: u8,
+    pub win_probability: f64,
+}
+
+// Plays a game out with every seat on the greedy-capture bot and returns the
+// first player to finish
+fn rollout(mut state: LudoGameState, rng: &mut StdRng) -> Option<usize> {
+    let mut moves = 0;
+    while state.winners.is_empty() && !state.is_over() && moves < MAX_ROLLOUT_MOVES {
+        let play = play(&mut GreedyCapture, &state, rng.gen_range(1..=6));
+        state = PlayGameParams::new(state, play)
+            .process()
+            .expect("Bots only play legal moves")
+            .state;
+        moves += 1;
+    }
+    state.winners.first().copied()
+}
+
+// Estimated chance of each player finishing first, from `rollouts` games
+// played out from `state`
+pub fn win_probabilities(state: &LudoGameState, rollouts: usize, seed: u64) -> Vec<f64> {
+    let state = simulation_state(state);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut wins = vec![0; state.players.len()];
+    for _ in 0..rollouts {
+        if let Some(winner) = rollout(state.clone(), &mut rng) {
+            wins[winner] += 1;
+        }
+    }
+    wins.into_iter()
+        .map(|w| w as f64 / rollouts.max(1) as f64)
+        .collect()
+}
+
+// The legal moves for `dice_roll`, best first. Every move is rolled out with
+// the same dice, so the comparison is not swamped by luck.
+pub fn rank_moves(
+    state: &LudoGameState,
+    dice_roll: u8,
+    rollouts: usize,
+    seed: u64,
+) -> Vec<MoveHint> {
+    if state.legal_moves(dice_roll).is_empty() {
+        return vec![];
+    }
+    let player = state.current_player;
+    let mut hints: Vec<MoveHint> = successors(&simulation_state(state), dice_roll)
+        .map(|(piece, next)| MoveHint {
+            piece,
+            win_probability: win_probabilities(&next, rollouts, seed)[player],
+        })
+        .collect();
+    hints.sort_by(|a, b| b.win_probability.total_cmp(&a.win_probability));
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ludo_core::{BoardLayout, Piece, PieceStatus, Player};
+
+    #[test]
+    fn probabilities_favour_the_leader() {
+        let players = BoardLayout::Classic
+            .colors()
+            .iter()
+            .map(|c| {
+                Player::new(
+                    format!("{:?}", c),
+                    *c,
+                    [0; 4].map(|_| Piece::new(-1, PieceStatus::Home)),
+                )
+            })
+            .collect();
+        let mut state = LudoGameState::new(BoardLayout::Classic, players);
+        let last_position = state.board.last_position() as i8;
+        state.players[2] = state.players[2].clone().with_public_key([2; 32]);
+        state.players[2].pieces =
+            [0; 4].map(|_| Piece::new(last_position - 1, PieceStatus::Active));
+        state.players[2].pieces[0] = Piece::new(last_position, PieceStatus::Win);
+
+        let probabilities = win_probabilities(&state, 200, 1);
+        assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(probabilities[2] > 0.9);
+
+        // Green is close to winning; Red's piece 0 can send it home with a 3
+        state.players[2].pieces = state.players[3].pieces.clone();
+        let green = 46;
+        let cell = state.path(1)[green];
+        let red = state.path(0).iter().position(|&c| c == cell).unwrap() as i8;
+        state.players[1].pieces = [0; 4].map(|_| Piece::new(last_position, PieceStatus::Win));
+        state.players[1].pieces[0] = Piece::new(green as i8, PieceStatus::Active);
+        state.players[0].pieces[0] = Piece::new(red - 3, PieceStatus::Active);
+        state.players[0].pieces[1] = Piece::new(30, PieceStatus::Active);
+        let hints = rank_moves(&state, 3, 200, 1);
+        assert_eq!(hints.len(), 2);
+        assert_eq!(hints[0].piece, 0);
+        assert!(hints[0].win_probability > hints[1].win_probability);
+    }
+}
@@ -0,0 +1,105 @@
+use ludo_core::{LudoGameState, Play};
+use std::fmt;
+use std::str::FromStr;
+
+pub mod analysis;
+pub mod heuristics;
+pub mod hints;
+pub mod search;
+pub mod tournament;
+
+pub use analysis::{moves, Move};
+pub use heuristics::{Defensive, FirstLegal, GreedyCapture, Race, RandomBot};
+pub use hints::{rank_moves, win_probabilities, MoveHint, DEFAULT_ROLLOUTS};
+pub use search::{Difficulty, Evaluation, Expectiminimax, WeightedEvaluation};
+
+// Decides which piece the player to move should move
+pub trait Strategy {
+    // Picks one of `state.legal_moves(dice_roll)`; only asked when that list
+    // is not empty, since otherwise the roll just passes the turn
+    fn choose(&mut self, state: &LudoGameState, dice_roll: u8) -> u8;
+}
+
+// Builds the play for the player to move. Bots only sit in seats without a
+// public key, so the play is unsigned.
+pub fn play(strategy: &mut dyn Strategy, state: &LudoGameState, dice_roll: u8) -> Play {
+    let piece_index = if state.legal_moves(dice_roll).is_empty() {
+        0
+    } else {
+        strategy.choose(state, dice_roll)
+    };
+    Play {
+        current_player: state.current_player,
+        dice_roll,
+        piece_index,
+        signature: None,
+    }
+}
+
+// The built-in strategies, selectable by name
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BotKind {
+    Random,
+    FirstLegal,
+    GreedyCapture,
+    Defensive,
+    Race,
+    Search(Difficulty),
+}
+
+impl BotKind {
+    pub const ALL: [BotKind; 9] = [
+        BotKind::Random,
+        BotKind::FirstLegal,
+        BotKind::GreedyCapture,
+        BotKind::Defensive,
+        BotKind::Race,
+        BotKind::Search(Difficulty::Easy),
+        BotKind::Search(Difficulty::Medium),
+        BotKind::Search(Difficulty::Hard),
+        BotKind::Search(Difficulty::Expert),
+    ];
+
+    // `seed` only matters for strategies that roll their own dice
+    pub fn build(&self, seed: u64) -> Box<dyn Strategy + Send> {
+        match self {
+            BotKind::Random => Box::new(RandomBot::new(seed)),
+            BotKind::FirstLegal => Box::new(FirstLegal),
+            BotKind::GreedyCapture => Box::new(GreedyCapture),
+            BotKind::Defensive => Box::new(Defensive),
+            BotKind::Race => Box::new(Race),
+            BotKind::Search(difficulty) => Box::new(Expectiminimax::new(*difficulty)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BotKind::Random => "random",
+            BotKind::FirstLegal => "first-legal",
+            BotKind::GreedyCapture => "greedy-capture",
+            BotKind::Defensive => "defensive",
+            BotKind::Race => "race",
+            BotKind::Search(Difficulty::Easy) => "search-easy",
+            BotKind::Search(Difficulty::Medium) => "search-medium",
+            BotKind::Search(Difficulty::Hard) => "search-hard",
+            BotKind::Search(Difficulty::Expert) => "search-expert",
+        }
+    }
+}
+
+impl fmt::Display for BotKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+impl FromStr for BotKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        BotKind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+            .ok_or_else(|| format!("Unknown bot {}", name))
+    }
+}
@@ -0,0 +1,306 @@
+use crate::analysis::threats;
+use crate::Strategy;
+use ludo_core::{LudoGameState, PieceStatus, Play, PlayGameParams};
+use std::time::{Duration, Instant};
+
+// Scores a position for one player; higher is better for them
+pub trait Evaluation {
+    fn score(&self, state: &LudoGameState, player: usize) -> f64;
+}
+
+// Linear mix of the usual Ludo features, per piece
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeightedEvaluation {
+    pub progress: f64, // Share of the path covered, 0 at home and 1 on the win cell
+    pub finished: f64,
+    pub at_home: f64,
+    pub at_risk: f64, // Active pieces an opponent could capture with their next roll
+}
+
+impl Default for WeightedEvaluation {
+    fn default() -> Self {
+        WeightedEvaluation {
+            progress: 10.0,
+            finished: 5.0,
+            at_home: -3.0,
+            at_risk: -4.0,
+        }
+    }
+}
+
+// Dwarfs every feature, so finishing or leaving the game decides the score
+const OUTCOME: f64 = 1000.0;
+
+impl Evaluation for WeightedEvaluation {
+    fn score(&self, state: &LudoGameState, player: usize) -> f64 {
+        if let Some(place) = state.winners.iter().position(|&w| w == player) {
+            return OUTCOME * (state.players.len() - place) as f64;
+        }
+        if state.eliminated.contains(&player) {
+            return -OUTCOME;
+        }
+
+        let path = state.path(player);
+        let last_position = state.board.last_position() as f64;
+        state.players[player]
+            .pieces
+            .iter()
+            .map(|piece| match piece.status {
+                PieceStatus::Home => self.at_home,
+                PieceStatus::Win => self.progress + self.finished,
+                PieceStatus::Active => {
+                    let cell = path[piece.position as usize];
+                    let risk = if threats(state, player, cell) > 0 {
+                        self.at_risk
+                    } else {
+                        0.0
+                    };
+                    self.progress * piece.position as f64 / last_position + risk
+                }
+            })
+            .sum()
+    }
+}
+
+// Search budgets for each difficulty level
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+    ];
+
+    // Deepest search, in turns, and the time allowed per move
+    pub fn budget(&self) -> (usize, Duration) {
+        match self {
+            Difficulty::Easy => (1, Duration::from_millis(10)),
+            Difficulty::Medium => (2, Duration::from_millis(50)),
+            Difficulty::Hard => (3, Duration::from_millis(250)),
+            Difficulty::Expert => (4, Duration::from_secs(1)),
+        }
+    }
+}
+
+// Expectiminimax over whole turns: the player to move picks a piece, then each
+// of the six rolls of the next turn is a chance node. With more than two
+// players the search is paranoid, every opponent plays against the bot.
+// Depth grows one turn at a time until the depth or time budget runs out, and
+// the deepest completed search decides the move.
+pub struct Expectiminimax<E: Evaluation = WeightedEvaluation> {
+    pub depth: usize,
+    pub time_budget: Option<Duration>,
+    pub evaluation: E,
+}
+
+impl Expectiminimax {
+    pub fn new(difficulty: Difficulty) -> Self {
+        let (depth, time_budget) = difficulty.budget();
+        Expectiminimax {
+            depth,
+            time_budget: Some(time_budget),
+            evaluation: WeightedEvaluation::default(),
+        }
+    }
+}
+
+impl<E: Evaluation> Expectiminimax<E> {
+    pub fn with_evaluation<F: Evaluation>(self, evaluation: F) -> Expectiminimax<F> {
+        Expectiminimax {
+            depth: self.depth,
+            time_budget: self.time_budget,
+            evaluation,
+        }
+    }
+
+    // Position value for `player`: their score against the best opponent's
+    fn evaluate(&self, state: &LudoGameState, player: usize) -> f64 {
+        let best_opponent = (0..state.players.len())
+            .filter(|&i| i != player && state.is_playing(i))
+            .map(|i| self.evaluation.score(state, i))
+            .fold(f64::NEG_INFINITY, f64::max);
+        let own = self.evaluation.score(state, player);
+        if best_opponent.is_finite() {
+            own - best_opponent
+        } else {
+            own
+        }
+    }
+
+    // Expected value over the next roll; None once the deadline has passed
+    fn chance(
+        &self,
+        state: &LudoGameState,
+        depth: usize,
+        player: usize,
+        deadline: Option<Instant>,
+    ) -> Option<f64> {
+        if depth == 0 || state.is_over() || !state.is_playing(player) {
+            return Some(self.evaluate(state, player));
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return None;
+        }
+
+        let mut total = 0.0;
+        for dice_roll in 1..=6 {
+            let values = successors(state, dice_roll)
+                .map(|(_, next)| self.chance(&next, depth - 1, player, deadline))
+                .collect::<Option<Vec<f64>>>()?;
+            total += if state.current_player == player {
+                values.into_iter().fold(f64::NEG_INFINITY, f64::max)
+            } else {
+                values.into_iter().fold(f64::INFINITY, f64::min)
+            };
+        }
+        Some(total / 6.0)
+    }
+
+    // Value of each legal move for the player to move, searched `depth` turns
+    fn values(
+        &self,
+        state: &LudoGameState,
+        dice_roll: u8,
+        depth: usize,
+        deadline: Option<Instant>,
+    ) -> Option<Vec<(u8, f64)>> {
+        let player = state.current_player;
+        successors(state, dice_roll)
+            .map(|(piece, next)| Some((piece, self.chance(&next, depth - 1, player, deadline)?)))
+            .collect()
+    }
+}
+
+impl<E: Evaluation> Strategy for Expectiminimax<E> {
+    fn choose(&mut self, state: &LudoGameState, dice_roll: u8) -> u8 {
+        let deadline = self.time_budget.map(|budget| Instant::now() + budget);
+        let state = &simulation_state(state);
+
+        // The shallowest search always completes, so there is a move to play
+        let mut best = self.values(state, dice_roll, 1, None).unwrap();
+        for depth in 2..=self.depth {
+            match self.values(state, dice_roll, depth, deadline) {
+                Some(values) => best = values,
+                None => break,
+            }
+        }
+        best.into_iter()
+            .fold((0, f64::NEG_INFINITY), |best, (piece, value)| {
+                if value > best.1 {
+                    (piece, value)
+                } else {
+                    best
+                }
+            })
+            .0
+    }
+}
+
+// Bots never hold the players' signing keys, so the search plays on a copy of
+// the state with every seat unkeyed
+pub(crate) fn simulation_state(state: &LudoGameState) -> LudoGameState {
+    let mut state = state.clone();
+    for player in &mut state.players {
+        player.public_key = None;
+    }
+    state
+}
+
+// The states reachable with `dice_roll`, one per legal move, or the single
+// state where the turn passes if there is none
+pub(crate) fn successors(
+    state: &LudoGameState,
+    dice_roll: u8,
+) -> impl Iterator<Item = (u8, LudoGameState)> + '_ {
+    let mut pieces = state.legal_moves(dice_roll);
+    if pieces.is_empty() {
+        pieces.push(0);
+    }
+    pieces.into_iter().map(move |piece_index| {
+        let play = Play {
+            current_player: state.current_player,
+            dice_roll,
+            piece_index,
+            signature: None,
+        };
+        (
+            piece_index,
+            PlayGameParams::new(state.clone(), play)
+                .process()
+                .expect("Only legal moves are searched")
+                .state,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ludo_core::{BoardLayout, Piece, Player};
+
+    fn state() -> LudoGameState {
+        let players = BoardLayout::Classic
+            .colors()
+            .iter()
+            .map(|c| {
+                Player::new(
+                    format!("{:?}", c),
+                    *c,
+                    [0; 4].map(|_| Piece::new(-1, PieceStatus::Home)),
+                )
+            })
+            .collect();
+        LudoGameState::new(BoardLayout::Classic, players)
+    }
+
+    #[test]
+    fn search_finishes_and_captures_when_it_can() {
+        let mut state = state();
+        let last_position = state.board.last_position() as i8;
+        state.players[0] = state.players[0].clone().with_public_key([1; 32]);
+        state.players[0].pieces[0] = Piece::new(10, PieceStatus::Active);
+        state.players[0].pieces[2] = Piece::new(last_position - 4, PieceStatus::Active);
+        for difficulty in Difficulty::ALL {
+            assert_eq!(Expectiminimax::new(difficulty).choose(&state, 4), 2);
+        }
+
+        // Green sits four steps ahead of Red's piece 0
+        let cell = state.path(0)[14];
+        let green = state.path(1).iter().position(|&c| c == cell).unwrap();
+        state.players[1].pieces[3] = Piece::new(green as i8, PieceStatus::Active);
+        state.players[0].pieces[2] = Piece::new(30, PieceStatus::Active);
+        let mut bot = Expectiminimax::new(Difficulty::Medium);
+        assert_eq!(bot.choose(&state, 4), 0);
+    }
+
+    // Counts finished pieces only
+    struct Finished;
+
+    impl Evaluation for Finished {
+        fn score(&self, state: &LudoGameState, player: usize) -> f64 {
+            state.players[player]
+                .pieces
+                .iter()
+                .filter(|p| p.status == PieceStatus::Win)
+                .count() as f64
+        }
+    }
+
+    #[test]
+    fn evaluation_is_pluggable() {
+        let mut state = state();
+        state.players[0].pieces[0] = Piece::new(3, PieceStatus::Active);
+        state.players[0].pieces[1] = Piece::new(20, PieceStatus::Active);
+        let mut bot = Expectiminimax::new(Difficulty::Easy).with_evaluation(Finished);
+        // Every move scores the same, so the first legal move is kept
+        assert_eq!(bot.choose(&state, 2), 0);
+    }
+}
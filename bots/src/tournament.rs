@@ -0,0 +1,252 @@
+use crate::{play, BotKind};
+use ludo_core::{
+    BoardLayout, Color, EndCondition, GameEvent, LudoGameState, PassReason, Piece, PieceStatus,
+    PlayGameParams, Player,
+};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+use std::thread;
+
+// Games still running after this many actions are abandoned and counted apart
+const MAX_MOVES: u32 = 10_000;
+
+// Fast, unproven games between bots, one bot per seat. Every game draws its
+// dice from its own seed, so results do not depend on the number of threads.
+#[derive(Clone, Debug)]
+pub struct Tournament {
+    pub board: BoardLayout,
+    pub bots: Vec<BotKind>, // In turn order
+    pub end_condition: EndCondition,
+    pub shuffle_colors: bool, // Deal colors to seats at random, to tell seat and path apart
+    pub seed: u64,
+}
+
+// What happened in a single game
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameSummary {
+    pub colors: Vec<Color>,    // Color of each seat
+    pub winner: Option<usize>, // First seat to finish
+    pub moves: u32,
+    pub captures: u32,
+    pub three_sixes: u32, // Turns forfeited on a third six
+}
+
+impl Tournament {
+    pub fn play_game(&self, index: u64) -> GameSummary {
+        let seed = self.seed.wrapping_add(index);
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let mut colors = self.board.colors()[..self.bots.len()].to_vec();
+        if self.shuffle_colors {
+            colors.shuffle(&mut rng);
+        }
+        let players = colors
+            .iter()
+            .enumerate()
+            .map(|(i, color)| {
+                Player::new(
+                    format!("Player {}", i + 1),
+                    *color,
+                    [0; 4].map(|_| Piece::new(-1, PieceStatus::Home)),
+                )
+            })
+            .collect();
+        let mut state =
+            LudoGameState::new(self.board, players).with_end_condition(self.end_condition);
+        let mut bots: Vec<_> = self
+            .bots
+            .iter()
+            .enumerate()
+            .map(|(seat, bot)| bot.build(seed ^ seat as u64))
+            .collect();
+
+        let mut summary = GameSummary {
+            colors,
+            winner: None,
+            moves: 0,
+            captures: 0,
+            three_sixes: 0,
+        };
+        while !state.is_over() && summary.moves < MAX_MOVES {
+            let dice_roll = rng.gen_range(1..=6);
+            let play = play(bots[state.current_player].as_mut(), &state, dice_roll);
+            let result = PlayGameParams::new(state, play)
+                .process()
+                .expect("Bots only play legal moves");
+            for event in &result.events {
+                match event {
+                    GameEvent::PieceCaptured { .. } => summary.captures += 1,
+                    GameEvent::TurnPassed {
+                        reason: PassReason::ThreeSixes,
+                        ..
+                    } => summary.three_sixes += 1,
+                    _ => {}
+                }
+            }
+            state = result.state;
+            summary.moves += 1;
+        }
+        summary.winner = state.winners.first().copied();
+        summary
+    }
+
+    // Plays games `0..games`, split over `threads` threads
+    pub fn run(&self, games: u64, threads: usize) -> Stats {
+        let threads = threads.max(1) as u64;
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|worker| {
+                    scope.spawn(move || {
+                        let mut stats = Stats::new(self);
+                        let mut index = worker;
+                        while index < games {
+                            stats.add(&self.play_game(index));
+                            index += threads;
+                        }
+                        stats
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .fold(Stats::new(self), |mut total, stats| {
+                    total.merge(&stats);
+                    total
+                })
+        })
+    }
+}
+
+// Totals over many games
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub bots: Vec<BotKind>,
+    pub colors: Vec<Color>, // Colors of the board, in its turn order
+    pub games: u64,
+    pub unfinished: u64, // Games abandoned after MAX_MOVES actions
+    pub wins_by_seat: Vec<u64>,
+    pub wins_by_color: Vec<u64>, // Indexed like `colors`
+    pub moves: u64,
+    pub captures: u64,
+    pub three_sixes: u64,
+}
+
+impl Stats {
+    pub fn new(tournament: &Tournament) -> Self {
+        let colors = tournament.board.colors().to_vec();
+        Stats {
+            bots: tournament.bots.clone(),
+            wins_by_seat: vec![0; tournament.bots.len()],
+            wins_by_color: vec![0; colors.len()],
+            colors,
+            games: 0,
+            unfinished: 0,
+            moves: 0,
+            captures: 0,
+            three_sixes: 0,
+        }
+    }
+
+    pub fn add(&mut self, game: &GameSummary) {
+        self.games += 1;
+        self.moves += game.moves as u64;
+        self.captures += game.captures as u64;
+        self.three_sixes += game.three_sixes as u64;
+        match game.winner {
+            Some(seat) => {
+                self.wins_by_seat[seat] += 1;
+                let color = self.colors.iter().position(|c| *c == game.colors[seat]);
+                self.wins_by_color[color.unwrap()] += 1;
+            }
+            None => self.unfinished += 1,
+        }
+    }
+
+    pub fn merge(&mut self, other: &Stats) {
+        self.games += other.games;
+        self.unfinished += other.unfinished;
+        self.moves += other.moves;
+        self.captures += other.captures;
+        self.three_sixes += other.three_sixes;
+        for (total, wins) in self.wins_by_seat.iter_mut().zip(&other.wins_by_seat) {
+            *total += wins;
+        }
+        for (total, wins) in self.wins_by_color.iter_mut().zip(&other.wins_by_color) {
+            *total += wins;
+        }
+    }
+
+    fn per_game(&self, count: u64) -> f64 {
+        count as f64 / self.games.max(1) as f64
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Games played:         {}", self.games)?;
+        writeln!(f, "Unfinished games:     {}", self.unfinished)?;
+        writeln!(
+            f,
+            "Average length:       {:.1} moves",
+            self.per_game(self.moves)
+        )?;
+        writeln!(
+            f,
+            "Captures per game:    {:.2}",
+            self.per_game(self.captures)
+        )?;
+        writeln!(
+            f,
+            "Three sixes per game: {:.3}",
+            self.per_game(self.three_sixes)
+        )?;
+        writeln!(f, "\nWin rate by seat:")?;
+        for (seat, (bot, wins)) in self.bots.iter().zip(&self.wins_by_seat).enumerate() {
+            writeln!(
+                f,
+                "  {} {:<15} {:>6.2}%",
+                seat + 1,
+                bot,
+                100.0 * self.per_game(*wins)
+            )?;
+        }
+        writeln!(f, "\nWin rate by color:")?;
+        for (color, wins) in self.colors.iter().zip(&self.wins_by_color) {
+            writeln!(
+                f,
+                "  {:<17} {:>6.2}%",
+                format!("{:?}", color),
+                100.0 * self.per_game(*wins)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results_depend_only_on_the_seed() {
+        let tournament = Tournament {
+            board: BoardLayout::Classic,
+            bots: vec![BotKind::Random, BotKind::Race, BotKind::GreedyCapture],
+            end_condition: EndCondition::FirstFinisher,
+            shuffle_colors: true,
+            seed: 42,
+        };
+        let single = tournament.run(40, 1);
+        assert_eq!(tournament.run(40, 3), single);
+        assert_eq!(single.games, 40);
+        assert_eq!(single.unfinished, 0);
+        assert_eq!(single.wins_by_seat.iter().sum::<u64>(), 40);
+        assert_eq!(single.wins_by_color.iter().sum::<u64>(), 40);
+        // Yellow never sits at a three-player table
+        assert_eq!(single.wins_by_color[3], 0);
+        assert!(single.moves > 40 * 100);
+    }
+}
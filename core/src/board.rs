@@ -0,0 +1,221 @@
+use crate::Color;
+use alloc::{format, string::String};
+use core::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+// Marker placed at the end of every classic path for the finishing cell
+pub const WIN: u8 = 100;
+
+// The six-arm board uses cells 0..107, so its finishing cell sits past them
+pub const SIX_PLAYER_WIN: u8 = 255;
+
+const CLASSIC_COLORS: [Color; 4] = [Color::Red, Color::Green, Color::Blue, Color::Yellow];
+
+const SIX_PLAYER_COLORS: [Color; 6] = [
+    Color::Red,
+    Color::Green,
+    Color::Blue,
+    Color::Yellow,
+    Color::Purple,
+    Color::Orange,
+];
+
+// Classic board cells are numbered row by row over the 15x15 grid, skipping the
+// home yards and the centre:
+// - 0..17 are the top arm (6 rows of 3)
+// - 18..53 are the left and right arms, interleaved row by row (3 rows of 6 + 6)
+// - 54..71 are the bottom arm (6 rows of 3)
+const RED_PATH: [u8; 57] = [
+    19, 20, 21, 22, 23, 15, 12, 9, 6, 3, 0, 1, 2, 5, 8, 11, 14, 17, 24, 25, 26, 27, 28, 29, 41, 53,
+    52, 51, 50, 49, 48, 56, 59, 62, 65, 68, 71, 70, 69, 66, 63, 60, 57, 54, 47, 46, 45, 44, 43, 42,
+    30, 31, 32, 33, 34, 35, WIN,
+];
+
+const GREEN_PATH: [u8; 57] = [
+    5, 8, 11, 14, 17, 24, 25, 26, 27, 28, 29, 41, 53, 52, 51, 50, 49, 48, 56, 59, 62, 65, 68, 71,
+    70, 69, 66, 63, 60, 57, 54, 47, 46, 45, 44, 43, 42, 30, 18, 19, 20, 21, 22, 23, 15, 12, 9, 6,
+    3, 0, 1, 4, 7, 10, 13, 16, WIN,
+];
+
+const BLUE_PATH: [u8; 57] = [
+    66, 63, 60, 57, 54, 47, 46, 45, 44, 43, 42, 30, 18, 19, 20, 21, 22, 23, 15, 12, 9, 6, 3, 0, 1,
+    2, 5, 8, 11, 14, 17, 24, 25, 26, 27, 28, 29, 41, 53, 52, 51, 50, 49, 48, 56, 59, 62, 65, 68,
+    71, 70, 67, 64, 61, 58, 55, WIN,
+];
+
+const YELLOW_PATH: [u8; 57] = [
+    52, 51, 50, 49, 48, 56, 59, 62, 65, 68, 71, 70, 69, 66, 63, 60, 57, 54, 47, 46, 45, 44, 43, 42,
+    30, 18, 19, 20, 21, 22, 23, 15, 12, 9, 6, 3, 0, 1, 2, 5, 8, 11, 14, 17, 24, 25, 26, 27, 28, 29,
+    41, 40, 39, 38, 37, 36, WIN,
+];
+
+const CLASSIC_PATHS: [&[u8]; 4] = [&RED_PATH, &GREEN_PATH, &BLUE_PATH, &YELLOW_PATH];
+
+// Each arm of the six-arm board has 3 lanes of 6 cells, numbered from the tip
+// of the arm towards the centre. Arm `k` owns cells `18 * k..18 * k + 18`:
+// - lane 0 (`+0..+5`) runs inwards and holds the arm's start cell at `+1`
+// - lane 1 (`+6..+11`) is the arm's home column, entered from the tip at `+6`
+// - lane 2 (`+12..+17`) runs outwards
+// Arms are numbered clockwise, so a piece leaving lane 0 of arm `k` continues
+// on lane 2 of arm `k + 1`, exactly as on the classic board.
+const SIX_PLAYER_ARMS: usize = 6;
+const ARM_CELLS: u8 = 18;
+pub const SIX_PLAYER_PATH_LEN: usize = 83;
+
+const fn six_player_path(arm: usize) -> [u8; SIX_PLAYER_PATH_LEN] {
+    let mut path = [0u8; SIX_PLAYER_PATH_LEN];
+    let mut i = 0;
+
+    // Remainder of the inward lane of the player's own arm
+    let base = arm as u8 * ARM_CELLS;
+    let mut depth = 1;
+    while depth < 6 {
+        path[i] = base + depth;
+        i += 1;
+        depth += 1;
+    }
+
+    // Full lap over the other five arms, then back out along the own arm
+    let mut step = 1;
+    while step <= SIX_PLAYER_ARMS {
+        let base = ((arm + step) % SIX_PLAYER_ARMS) as u8 * ARM_CELLS;
+        let mut depth = 6;
+        while depth > 0 {
+            depth -= 1;
+            path[i] = base + 12 + depth;
+            i += 1;
+        }
+        path[i] = base + 6;
+        i += 1;
+        if step < SIX_PLAYER_ARMS {
+            let mut depth = 0;
+            while depth < 6 {
+                path[i] = base + depth;
+                i += 1;
+                depth += 1;
+            }
+        }
+        step += 1;
+    }
+
+    // Home column
+    let mut depth = 7;
+    while depth < 12 {
+        path[i] = base + depth;
+        i += 1;
+        depth += 1;
+    }
+    path[i] = SIX_PLAYER_WIN;
+    path
+}
+
+static SIX_PLAYER_PATHS: [[u8; SIX_PLAYER_PATH_LEN]; SIX_PLAYER_ARMS] = [
+    six_player_path(0),
+    six_player_path(1),
+    six_player_path(2),
+    six_player_path(3),
+    six_player_path(4),
+    six_player_path(5),
+];
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum BoardLayout {
+    #[default]
+    Classic, // Four arms, cells 0..71
+    SixPlayer, // Six arms, cells 0..107
+}
+
+impl BoardLayout {
+    // Colors seated on this board, in turn order
+    pub fn colors(&self) -> &'static [Color] {
+        match self {
+            BoardLayout::Classic => &CLASSIC_COLORS,
+            BoardLayout::SixPlayer => &SIX_PLAYER_COLORS,
+        }
+    }
+
+    pub fn max_players(&self) -> usize {
+        self.colors().len()
+    }
+
+    // Number of board cells, excluding home yards and the finishing cell
+    pub fn cell_count(&self) -> usize {
+        match self {
+            BoardLayout::Classic => 72,
+            BoardLayout::SixPlayer => SIX_PLAYER_ARMS * ARM_CELLS as usize,
+        }
+    }
+
+    pub fn win_cell(&self) -> u8 {
+        match self {
+            BoardLayout::Classic => WIN,
+            BoardLayout::SixPlayer => SIX_PLAYER_WIN,
+        }
+    }
+
+    pub fn supports(&self, color: &Color) -> bool {
+        self.colors().contains(color)
+    }
+
+    // The sequence of board cells a piece of `color` follows, ending on the win cell
+    pub fn path(&self, color: &Color) -> &'static [u8] {
+        let seat = self
+            .colors()
+            .iter()
+            .position(|c| c == color)
+            .expect("Color is not seated on this board");
+        match self {
+            BoardLayout::Classic => CLASSIC_PATHS[seat],
+            BoardLayout::SixPlayer => &SIX_PLAYER_PATHS[seat],
+        }
+    }
+
+    // Index of the finishing cell on every path
+    pub fn last_position(&self) -> usize {
+        match self {
+            BoardLayout::Classic => RED_PATH.len() - 1,
+            BoardLayout::SixPlayer => SIX_PLAYER_PATH_LEN - 1,
+        }
+    }
+}
+
+// Command line names: `classic` or `six-player`
+impl FromStr for BoardLayout {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "classic" => Ok(BoardLayout::Classic),
+            "six-player" => Ok(BoardLayout::SixPlayer),
+            _ => Err(format!("Unknown board {}", name)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn six_player_paths_mirror_classic_geometry() {
+        let board = BoardLayout::SixPlayer;
+        for color in board.colors() {
+            let path = board.path(color);
+            assert_eq!(path.len(), SIX_PLAYER_PATH_LEN);
+            assert_eq!(path[board.last_position()], board.win_cell());
+
+            let mut cells = path[..board.last_position()].to_vec();
+            assert!(cells.iter().all(|&c| (c as usize) < board.cell_count()));
+            cells.sort();
+            cells.dedup();
+            assert_eq!(cells.len(), board.last_position(), "path revisits a cell");
+        }
+
+        // Every seat shares the same 77 track cells before its home column
+        let red = board.path(&Color::Red);
+        let orange = board.path(&Color::Orange);
+        assert_eq!(red[0], 1);
+        assert_eq!(orange[0], 5 * ARM_CELLS + 1);
+        assert!(orange[..77].contains(&red[0]));
+    }
+}
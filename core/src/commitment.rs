@@ -0,0 +1,14 @@
+use crate::digest::{sha256, Digest};
+use crate::LudoGameState;
+
+// Secret shared privately between the players of a hidden game
+pub type Salt = [u8; 32];
+
+// Hiding commitment to a state. Ludo positions have little entropy, so a plain
+// hash can be brute-forced back into the board; mixing in a secret salt
+// prevents that. Anyone given the salt can recompute it to settle a dispute.
+pub fn salted_digest(salt: &Salt, state: &LudoGameState) -> Digest {
+    let mut bytes = salt.to_vec();
+    bytes.extend_from_slice(state.digest().as_bytes());
+    sha256(&bytes)
+}
@@ -0,0 +1,192 @@
+use crate::merkle::{MetadataLeaf, PlayerLeaf};
+use crate::packed::{board_code, color_code, end_condition_code, status_code};
+use crate::{LudoGameState, PublicKey};
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+
+// A SHA-256 hash. Serialized as eight words, the same layout as the risc0
+// digest type, so journals written by the guests keep their format.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Digest([u32; 8]);
+
+impl Digest {
+    pub const ZERO: Digest = Digest([0; 8]);
+
+    pub const fn new(words: [u32; 8]) -> Self {
+        Digest(words)
+    }
+
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Digest(bytemuck::cast(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.0)
+    }
+
+    pub fn as_words(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl From<[u32; 8]> for Digest {
+    fn from(words: [u32; 8]) -> Self {
+        Digest(words)
+    }
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_bytes()
+            .iter()
+            .try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+impl fmt::Debug for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Digest({})", self)
+    }
+}
+
+// SHA-256 of `bytes`. Guests patch `sha2` with the risc0 fork, which runs it
+// on the zkVM's SHA accelerator.
+pub fn sha256(bytes: &[u8]) -> Digest {
+    Digest::from_bytes(Sha256::digest(bytes).into())
+}
+
+// Version of the canonical state hash. Bump it whenever the tree layout or the
+// encoding of a leaf changes, so digests from different versions never collide.
+pub const STATE_DIGEST_VERSION: u32 = 3;
+
+// Heads every state digest, followed by the version
+const STATE_DOMAIN: &[u8] = b"LUDO";
+
+// Binds the top node of a state tree to the domain and version
+pub(crate) fn finalize_root(top: &Digest) -> Digest {
+    let mut bytes = STATE_DOMAIN.to_vec();
+    bytes.extend_from_slice(&STATE_DIGEST_VERSION.to_le_bytes());
+    bytes.extend_from_slice(top.as_bytes());
+    sha256(&bytes)
+}
+
+// The digest is computed over this explicit encoding rather than a serde
+// format, so it stays fixed across serializer upgrades and can be recomputed
+// outside Rust. Indices and small enums take one byte, the move number is a
+// little-endian u32, and lists and names are prefixed with their length. An
+// optional key is a zero byte, or a one byte followed by the 32 key bytes.
+fn push_indices(bytes: &mut Vec<u8>, indices: &[usize]) {
+    bytes.push(indices.len() as u8);
+    bytes.extend(indices.iter().map(|&i| i as u8));
+}
+
+fn push_key(bytes: &mut Vec<u8>, key: &Option<PublicKey>) {
+    match key {
+        Some(key) => {
+            bytes.push(1);
+            bytes.extend_from_slice(key);
+        }
+        None => bytes.push(0),
+    }
+}
+
+impl MetadataLeaf {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = self.game_id.0.to_vec();
+        bytes.push(board_code(self.board));
+        bytes.push(self.names.len() as u8);
+        for name in &self.names {
+            bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(name.as_bytes());
+        }
+        bytes.push(self.current_player as u8);
+        bytes.push(self.dice_roll);
+        push_indices(&mut bytes, &self.winners);
+        bytes.push(self.sixes);
+        bytes.extend_from_slice(&end_condition_code(self.end_condition));
+        push_indices(&mut bytes, &self.eliminated);
+        bytes.extend_from_slice(&self.move_number.to_le_bytes());
+        push_key(&mut bytes, &self.arbiter);
+        bytes
+    }
+}
+
+impl PlayerLeaf {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = vec![
+            self.player as u8,
+            board_code(self.board),
+            color_code(self.color),
+        ];
+        for piece in &self.pieces {
+            bytes.push(status_code(&piece.status));
+            bytes.push(piece.position as u8);
+        }
+        push_key(&mut bytes, &self.public_key);
+        bytes
+    }
+}
+
+impl LudoGameState {
+    // Canonical hash of a state, computed the same way in the zkVM and natively.
+    // It is the root of the state's Merkle tree, so a single player's pieces can
+    // be disclosed against it, see `merkle`.
+    pub fn digest(&self) -> Digest {
+        finalize_root(&self.merkle_top())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoardLayout, EndCondition, GameId, Piece, PieceStatus, Player};
+    use alloc::{format, string::ToString};
+
+    fn state(board: BoardLayout) -> LudoGameState {
+        let players = board
+            .colors()
+            .iter()
+            .map(|c| {
+                Player::new(
+                    format!("{:?}", c),
+                    *c,
+                    [0; 4].map(|_| Piece::new(-1, PieceStatus::Home)),
+                )
+            })
+            .collect();
+        LudoGameState::new(board, players)
+    }
+
+    // Changing any of these vectors breaks every commitment already on chain;
+    // bump STATE_DIGEST_VERSION instead
+    #[test]
+    fn digest_matches_golden_vectors() {
+        let initial = state(BoardLayout::Classic);
+        assert_eq!(
+            initial.digest().to_string(),
+            "5cf348cf91b284abe714bf5070b092bd1adbc4131bc562a86bd1b85a32e51b36"
+        );
+
+        let mut midgame = state(BoardLayout::Classic)
+            .with_game_id(GameId([9; 16]))
+            .with_end_condition(EndCondition::TopN(2));
+        midgame.players[0].pieces[0] = Piece::new(12, PieceStatus::Active);
+        midgame.players[2].pieces[3] = Piece::new(56, PieceStatus::Win);
+        midgame.players[1].public_key = Some([5; 32]);
+        midgame.arbiter = Some([6; 32]);
+        midgame.current_player = 1;
+        midgame.move_number = 17;
+        assert_eq!(
+            midgame.digest().to_string(),
+            "e64428069d8829a71874698b06102aca990706583ccf286b895076a8b5bce8ef"
+        );
+
+        let six_player = state(BoardLayout::SixPlayer);
+        assert_eq!(
+            six_player.digest().to_string(),
+            "09f30bdccccf0682234b1730739f055c9d1f9bbe7b185fe3133cea9cc4275259"
+        );
+    }
+}
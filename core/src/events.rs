@@ -0,0 +1,95 @@
+use crate::{Action, LudoGameState};
+use alloc::{vec, vec::Vec};
+use serde::{Deserialize, Serialize};
+
+// Identifies a single piece on the board
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PieceRef {
+    pub player: usize,
+    pub piece: u8,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PassReason {
+    MoveComplete,   // The move ended the turn without a bonus roll
+    NoLegalMove,    // No piece could move with the roll
+    ThreeSixes,     // Third consecutive six forfeits the turn
+    PlayerWon,      // The player brought their last piece home
+    PlayerResigned, // The player left the game on their own turn
+}
+
+// Something that happened while processing an action, in the order it happened.
+// Positions are indices into the moving player's path.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum GameEvent {
+    DiceRolled {
+        player: usize,
+        value: u8,
+    },
+    PieceEntered {
+        piece: PieceRef,
+    },
+    PieceMoved {
+        piece: PieceRef,
+        from: i8,
+        to: i8,
+    },
+    PieceCaptured {
+        by: PieceRef,
+        victim: PieceRef,
+    },
+    PieceFinished {
+        piece: PieceRef,
+    },
+    TurnPassed {
+        player: usize,
+        next_player: usize,
+        reason: PassReason,
+    },
+    PlayerWon {
+        player: usize,
+    },
+    PlayerEliminated {
+        player: usize,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MoveRecord {
+    pub move_number: u32, // 1 for the first action of the game
+    pub action: Action,
+    pub events: Vec<GameEvent>,
+}
+
+// Ordered history of a game, enough to replay or animate it from the start
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub initial_state: LudoGameState,
+    pub moves: Vec<MoveRecord>,
+}
+
+impl GameRecord {
+    pub fn new(initial_state: LudoGameState) -> Self {
+        GameRecord {
+            initial_state,
+            moves: vec![],
+        }
+    }
+
+    // Appends an action and the events it produced, numbering it after the last move
+    pub fn record(&mut self, action: Action, events: Vec<GameEvent>) -> &MoveRecord {
+        self.moves.push(MoveRecord {
+            move_number: self.moves.len() as u32 + 1,
+            action,
+            events,
+        });
+        self.moves.last().unwrap()
+    }
+
+    // Iterates over every event of the game in order, with its move number
+    pub fn events(&self) -> impl Iterator<Item = (u32, &GameEvent)> {
+        self.moves
+            .iter()
+            .flat_map(|m| m.events.iter().map(move |e| (m.move_number, e)))
+    }
+}
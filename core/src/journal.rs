@@ -0,0 +1,303 @@
+use crate::words::{self, WordsError};
+use crate::{Action, Digest, GameEvent, PieceRef, Play, PlayGameParams, PlayGameResult};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+// Layout version written at the start of every new play journal
+pub const PLAY_COMMIT_VERSION: u32 = 4;
+
+// Layout version of play journals whose resignations carried no signature
+const PLAY_COMMIT_V2_VERSION: u32 = 2;
+
+// Layout version of play journals for games with salted state commitments
+pub const HIDDEN_PLAY_COMMIT_VERSION: u32 = 3;
+
+// Journals committed before versioning were a fixed 20 words: two digests of
+// 8 words, the current player (a usize, so two words), dice roll and piece index
+const LEGACY_PLAY_COMMIT_LEN: usize = 20 * 4;
+
+// Identifies a game across every receipt it produces
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct GameId(pub [u8; 16]);
+
+// Where a piece ended up after a move, as an index into its owner's path
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PiecePosition {
+    pub piece: PieceRef,
+    pub position: i8,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PlayGameCommit {
+    pub version: u32, // Always PLAY_COMMIT_VERSION
+    pub game_id: GameId,
+    pub move_number: u32, // 1 for the first action of the game
+    pub old_state: Digest,
+    pub new_state: Digest,
+    pub action: Action,
+    pub next_player: usize,
+    pub moved_piece: Option<PiecePosition>, // None if the turn passed without a move
+    pub captured: Option<PieceRef>,
+    pub winners: Vec<usize>, // Winners so far, in finishing order
+}
+
+impl PlayGameCommit {
+    // Builds the journal for a processed action from the hashes of both states
+    pub fn new(
+        params: &PlayGameParams,
+        result: &PlayGameResult,
+        old_state: Digest,
+        new_state: Digest,
+    ) -> Self {
+        PlayGameCommit {
+            version: PLAY_COMMIT_VERSION,
+            game_id: result.state.game_id,
+            move_number: result.state.move_number,
+            old_state,
+            new_state,
+            action: params.action.clone(),
+            next_player: result.next_player,
+            moved_piece: result.moved_piece(),
+            captured: result.captured(),
+            winners: result.state.winners.clone(),
+        }
+    }
+}
+
+// Play journal of a game with salted state commitments. Only what is needed to
+// chain receipts is public; the move itself stays between the players.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HiddenPlayCommit {
+    pub version: u32, // Always HIDDEN_PLAY_COMMIT_VERSION
+    pub game_id: GameId,
+    pub move_number: u32,
+    pub old_state: Digest, // Salted commitments, see `salted_digest`
+    pub new_state: Digest,
+}
+
+impl HiddenPlayCommit {
+    pub fn new(result: &PlayGameResult, old_state: Digest, new_state: Digest) -> Self {
+        HiddenPlayCommit {
+            version: HIDDEN_PLAY_COMMIT_VERSION,
+            game_id: result.state.game_id,
+            move_number: result.state.move_number,
+            old_state,
+            new_state,
+        }
+    }
+}
+
+// Play journal layout used before versioning
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PlayGameCommitV1 {
+    pub old_state: Digest,
+    pub new_state: Digest,
+    pub current_player: usize,
+    pub dice_roll: u8,
+    pub piece_index: u8,
+}
+
+// Action of a version 2 journal. Plays have kept their layout since.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ActionV2 {
+    Play(Play),
+    Resign { player: usize },
+}
+
+// Play journal layout of version 2, frozen
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PlayGameCommitV2 {
+    pub version: u32,
+    pub game_id: GameId,
+    pub move_number: u32,
+    pub old_state: Digest,
+    pub new_state: Digest,
+    pub action: ActionV2,
+    pub next_player: usize,
+    pub moved_piece: Option<PiecePosition>,
+    pub captured: Option<PieceRef>,
+    pub winners: Vec<usize>,
+}
+
+// A decoded play journal of any supported layout, named after its version
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PlayJournal {
+    V1(PlayGameCommitV1),
+    V2(PlayGameCommitV2),
+    V3(HiddenPlayCommit),
+    V4(PlayGameCommit),
+}
+
+impl PlayJournal {
+    // Decodes the journal bytes of a play receipt, see `words`
+    pub fn decode(bytes: &[u8]) -> Result<Self, WordsError> {
+        if bytes.len() == LEGACY_PLAY_COMMIT_LEN {
+            return Ok(PlayJournal::V1(words::from_slice(bytes)?));
+        }
+
+        let version: u32 = words::from_slice(bytes.get(..4).unwrap_or_default())?;
+        match version {
+            PLAY_COMMIT_V2_VERSION => Ok(PlayJournal::V2(words::from_slice(bytes)?)),
+            HIDDEN_PLAY_COMMIT_VERSION => Ok(PlayJournal::V3(words::from_slice(bytes)?)),
+            PLAY_COMMIT_VERSION => Ok(PlayJournal::V4(words::from_slice(bytes)?)),
+            _ => Err(serde::de::Error::custom("Unsupported play journal version")),
+        }
+    }
+
+    // Game and move number, missing from pre-versioning journals
+    pub fn game_move(&self) -> Option<(GameId, u32)> {
+        match self {
+            PlayJournal::V1(_) => None,
+            PlayJournal::V2(commit) => Some((commit.game_id, commit.move_number)),
+            PlayJournal::V3(commit) => Some((commit.game_id, commit.move_number)),
+            PlayJournal::V4(commit) => Some((commit.game_id, commit.move_number)),
+        }
+    }
+
+    pub fn old_state(&self) -> Digest {
+        match self {
+            PlayJournal::V1(commit) => commit.old_state,
+            PlayJournal::V2(commit) => commit.old_state,
+            PlayJournal::V3(commit) => commit.old_state,
+            PlayJournal::V4(commit) => commit.old_state,
+        }
+    }
+
+    pub fn new_state(&self) -> Digest {
+        match self {
+            PlayJournal::V1(commit) => commit.new_state,
+            PlayJournal::V2(commit) => commit.new_state,
+            PlayJournal::V3(commit) => commit.new_state,
+            PlayJournal::V4(commit) => commit.new_state,
+        }
+    }
+}
+
+impl PlayGameResult {
+    // The piece moved by this action and where it landed
+    pub fn moved_piece(&self) -> Option<PiecePosition> {
+        self.events.iter().rev().find_map(|event| match event {
+            GameEvent::PieceMoved { piece, to, .. } => Some(PiecePosition {
+                piece: *piece,
+                position: *to,
+            }),
+            GameEvent::PieceEntered { piece } => Some(PiecePosition {
+                piece: *piece,
+                position: 0,
+            }),
+            _ => None,
+        })
+    }
+
+    // The opponent piece sent home by this action, if any
+    pub fn captured(&self) -> Option<PieceRef> {
+        self.events.iter().find_map(|event| match event {
+            GameEvent::PieceCaptured { victim, .. } => Some(*victim),
+            _ => None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoardLayout, Color, LudoGameState, Piece, PieceStatus, Play, Player, Resign};
+    use alloc::{format, vec};
+    use risc0_zkvm::serde::to_vec;
+
+    fn bytes(words: Vec<u32>) -> Vec<u8> {
+        words.iter().flat_map(|w| w.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn decodes_legacy_and_current_layouts() {
+        let legacy = PlayGameCommitV1 {
+            old_state: Digest::new([1; 8]),
+            new_state: Digest::new([2; 8]),
+            current_player: 0,
+            dice_roll: 6,
+            piece_index: 0,
+        };
+        let decoded = PlayJournal::decode(&bytes(to_vec(&legacy).unwrap())).unwrap();
+        assert_eq!(decoded, PlayJournal::V1(legacy));
+
+        let home = Piece::new(-1, PieceStatus::Home);
+        let players = [Color::Red, Color::Green]
+            .iter()
+            .map(|c| Player::new(format!("{:?}", c), *c, [0; 4].map(|_| home.clone())))
+            .collect();
+        let state = LudoGameState::new(BoardLayout::Classic, players);
+        let play = Play {
+            current_player: 0,
+            dice_roll: 6,
+            piece_index: 2,
+            signature: None,
+        };
+        let params = PlayGameParams::new(state.with_game_id(GameId([7; 16])), play);
+        let result = params.process().unwrap();
+        let commit = PlayGameCommit::new(&params, &result, Digest::ZERO, Digest::ZERO);
+        assert_eq!(commit.game_id, GameId([7; 16]));
+        assert_eq!(commit.move_number, 1);
+        assert_eq!(
+            commit.moved_piece,
+            Some(PiecePosition {
+                piece: PieceRef {
+                    player: 0,
+                    piece: 2
+                },
+                position: 0
+            })
+        );
+
+        let decoded = PlayJournal::decode(&bytes(to_vec(&commit).unwrap())).unwrap();
+        assert_eq!(decoded, PlayJournal::V4(commit.clone()));
+
+        // Version 2 resigned players without a signature
+        let v2 = PlayGameCommitV2 {
+            version: 2,
+            game_id: commit.game_id,
+            move_number: 3,
+            old_state: Digest::new([1; 8]),
+            new_state: Digest::new([2; 8]),
+            action: ActionV2::Resign { player: 1 },
+            next_player: 0,
+            moved_piece: None,
+            captured: None,
+            winners: vec![],
+        };
+        let decoded = PlayJournal::decode(&bytes(to_vec(&v2).unwrap())).unwrap();
+        assert_eq!(decoded.game_move(), Some((GameId([7; 16]), 3)));
+        assert_eq!(decoded, PlayJournal::V2(v2));
+
+        // Signatures are byte lists of any length, so resignations vary in size
+        let resign = Resign {
+            player: 1,
+            signature: Some(vec![9; 5]),
+        };
+        let mut commit = commit;
+        commit.action = Action::Resign(resign);
+        let decoded = PlayJournal::decode(&bytes(to_vec(&commit).unwrap())).unwrap();
+        assert_eq!(decoded, PlayJournal::V4(commit));
+
+        let hidden = HiddenPlayCommit::new(&result, Digest::ZERO, Digest::ZERO);
+        let decoded = PlayJournal::decode(&bytes(to_vec(&hidden).unwrap())).unwrap();
+        assert_eq!(decoded.game_move(), Some((GameId([7; 16]), 1)));
+        assert_eq!(decoded, PlayJournal::V3(hidden));
+    }
+
+    // The decoder is written by hand, so pin it to the guests' serializer
+    #[test]
+    fn guest_serializer_writes_the_golden_words() {
+        use crate::words::golden;
+        assert_eq!(to_vec(&golden::init().0).unwrap(), golden::init().1);
+        assert_eq!(to_vec(&golden::play_v1().0).unwrap(), golden::play_v1().1);
+        assert_eq!(to_vec(&golden::play_v2().0).unwrap(), golden::play_v2().1);
+        assert_eq!(to_vec(&golden::play_v3().0).unwrap(), golden::play_v3().1);
+        assert_eq!(to_vec(&golden::play_v4().0).unwrap(), golden::play_v4().1);
+        assert_eq!(to_vec(&golden::winners().0).unwrap(), golden::winners().1);
+        assert_eq!(
+            to_vec(&golden::disclosure().0).unwrap(),
+            golden::disclosure().1
+        );
+    }
+}
@@ -1,12 +1,42 @@
-// #![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use risc0_zkp::core::digest::Digest;
-// use risc0_zkvm::{
-//     serde::to_vec,
-//     sha::{Impl, Sha256},
-// };
+extern crate alloc;
+
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt;
 use serde::{Deserialize, Serialize};
 
+pub mod board;
+pub mod commitment;
+pub mod digest;
+pub mod events;
+pub mod journal;
+pub mod merkle;
+pub mod notation;
+pub mod outcome;
+pub mod packed;
+pub mod render;
+pub mod signing;
+pub mod words;
+
+pub use board::BoardLayout;
+pub use commitment::{salted_digest, Salt};
+pub use digest::{Digest, STATE_DIGEST_VERSION};
+pub use events::{GameEvent, GameRecord, MoveRecord, PassReason, PieceRef};
+pub use journal::{
+    ActionV2, GameId, HiddenPlayCommit, PiecePosition, PlayGameCommit, PlayGameCommitV1,
+    PlayGameCommitV2, PlayJournal, HIDDEN_PLAY_COMMIT_VERSION, PLAY_COMMIT_VERSION,
+};
+pub use merkle::{
+    DisclosureCommit, DisclosureParams, MerkleProof, MetadataLeaf, PlayerLeaf, Statement,
+};
+pub use notation::{Notation, NotationError, RecordError};
+pub use outcome::{EndCondition, Placement};
+pub use packed::{PackedError, PackedState, Roster, PACKED_STATE_LEN, PACKED_STATE_WORDS};
+pub use render::{render_ascii, render_svg, BoardGeometry, GridPos};
+pub use signing::PublicKey;
+pub use words::WordsError;
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum PieceStatus {
     Home,   // Piece is in starting position
@@ -14,12 +44,14 @@ pub enum PieceStatus {
     Win,    // Piece has reached the end
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Color {
     Red,
     Green,
     Blue,
     Yellow,
+    Purple, // Six-player board only
+    Orange, // Six-player board only
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -39,108 +71,285 @@ pub struct Player {
     pub name: String,
     pub color: Color,
     pub pieces: [Piece; 4], // Each player has 4 pieces
-    pub path: Vec<u8>,      // The sequence of board positions this player must follow
+    #[serde(default)]
+    pub public_key: Option<PublicKey>, // Moves must be signed with this key when set
 }
 
 impl Player {
-    pub fn new(name: String, color: Color, pieces: [Piece; 4], path: Vec<u8>) -> Self {
+    pub fn new(name: String, color: Color, pieces: [Piece; 4]) -> Self {
         Player {
             name,
             color,
             pieces,
-            path,
+            public_key: None,
         }
     }
+
+    pub fn with_public_key(mut self, public_key: PublicKey) -> Self {
+        self.public_key = Some(public_key);
+        self
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct LudoGameState {
+    #[serde(default)]
+    pub game_id: GameId, // Random per game, so identical positions in different games hash apart
+    #[serde(default)]
+    pub board: BoardLayout, // Board geometry; each player's path is looked up by color
     pub players: Vec<Player>,
     pub current_player: usize,
     pub dice_roll: u8,
     pub winners: Vec<usize>, // Tracks players who have won
     pub sixes: u8,           // Counts consecutive sixes rolled
+    #[serde(default)]
+    pub end_condition: EndCondition, // When the game is considered over
+    #[serde(default)]
+    pub eliminated: Vec<usize>, // Players who resigned or forfeited, in order
+    #[serde(default)]
+    pub move_number: u32, // Actions processed so far
+    #[serde(default)]
+    pub arbiter: Option<PublicKey>, // May forfeit players whose turn expired, see `Resign`
 }
 
 impl LudoGameState {
+    pub fn new(board: BoardLayout, players: Vec<Player>) -> Self {
+        assert!(
+            players.len() >= 2 && players.len() <= board.max_players(),
+            "Unsupported number of players for this board"
+        );
+        assert!(
+            players.iter().all(|p| board.supports(&p.color)),
+            "Player color is not seated on this board"
+        );
+        LudoGameState {
+            game_id: GameId::default(),
+            board,
+            players,
+            current_player: 0,
+            dice_roll: 0,
+            winners: vec![],
+            sixes: 0,
+            end_condition: EndCondition::default(),
+            eliminated: vec![],
+            move_number: 0,
+            arbiter: None,
+        }
+    }
+
+    pub fn with_game_id(mut self, game_id: GameId) -> Self {
+        self.game_id = game_id;
+        self
+    }
+
+    pub fn with_end_condition(mut self, end_condition: EndCondition) -> Self {
+        self.end_condition = end_condition;
+        self
+    }
+
+    pub fn with_arbiter(mut self, arbiter: PublicKey) -> Self {
+        self.arbiter = Some(arbiter);
+        self
+    }
+
+    // The sequence of board cells the given player must follow
+    pub fn path(&self, player_index: usize) -> &'static [u8] {
+        self.board.path(&self.players[player_index].color)
+    }
+
     // Handles dice roll and determines if player's turn should end
     // Returns true if the turn should end automatically
-    pub fn roll_dice(&mut self, dice_roll: u8) -> bool {
+    pub fn roll_dice(&mut self, dice_roll: u8, events: &mut Vec<GameEvent>) -> bool {
         self.dice_roll = dice_roll;
+        events.push(GameEvent::DiceRolled {
+            player: self.current_player,
+            value: dice_roll,
+        });
 
         if dice_roll == 6 {
             self.sixes += 1;
         }
 
-        let active_pieces = self.players[self.current_player]
-            .pieces
-            .iter_mut()
-            .filter(|p| p.status == PieceStatus::Active)
-            .collect::<Vec<&mut Piece>>();
+        let active_pieces = (0..4)
+            .filter(|&i| self.players[self.current_player].pieces[i].status == PieceStatus::Active)
+            .collect::<Vec<usize>>();
 
-        if (active_pieces.len() == 0 && dice_roll != 6) || self.sixes >= 3 {
-            self.current_player = self.get_next_turn();
-            self.sixes = 0;
+        if self.sixes >= 3 {
+            self.pass_turn(PassReason::ThreeSixes, events);
+            return true;
+        } else if active_pieces.is_empty() && dice_roll != 6 {
+            self.pass_turn(PassReason::NoLegalMove, events);
             return true;
         } else if active_pieces.len() == 1 && dice_roll != 6 {
-            self.move_piece(0);
+            if self.can_move(active_pieces[0], dice_roll) {
+                self.move_piece(active_pieces[0], events);
+            } else {
+                self.pass_turn(PassReason::NoLegalMove, events);
+            }
             return true;
         }
 
         false
     }
 
-    // Determines the next player's turn, skipping any winners
+    // Returns true if the current player's piece can move with the given roll:
+    // - Active pieces may not overshoot the win cell
+    // - Pieces at home need a 6 to enter the board
+    pub fn can_move(&self, piece_index: usize, dice_roll: u8) -> bool {
+        let piece = &self.players[self.current_player].pieces[piece_index];
+        match piece.status {
+            PieceStatus::Active => {
+                piece.position as usize + dice_roll as usize <= self.board.last_position()
+            }
+            PieceStatus::Home => dice_roll == 6,
+            PieceStatus::Win => false,
+        }
+    }
+
+    // Indices of the current player's pieces that can move with the given roll.
+    // An empty list means the roll can only pass the turn.
+    pub fn legal_moves(&self, dice_roll: u8) -> Vec<u8> {
+        if !self.is_playing(self.current_player) || (dice_roll == 6 && self.sixes >= 2) {
+            return vec![];
+        }
+        (0..4)
+            .filter(|&i| self.can_move(i, dice_roll))
+            .map(|i| i as u8)
+            .collect()
+    }
+
+    // Hands the turn to the next player still in the game
+    pub fn pass_turn(&mut self, reason: PassReason, events: &mut Vec<GameEvent>) {
+        let player = self.current_player;
+        self.current_player = self.get_next_turn();
+        self.sixes = 0;
+        events.push(GameEvent::TurnPassed {
+            player,
+            next_player: self.current_player,
+            reason,
+        });
+    }
+
+    // Returns true if the player has neither finished nor left the game
+    pub fn is_playing(&self, player_index: usize) -> bool {
+        !self.winners.contains(&player_index) && !self.eliminated.contains(&player_index)
+    }
+
+    // Determines the next player's turn, skipping any winners and eliminated players
     pub fn get_next_turn(&self) -> usize {
         let mut next_index = (self.current_player + 1) % self.players.len();
-        while self.winners.contains(&next_index) {
+        for _ in 0..self.players.len() {
+            if self.is_playing(next_index) {
+                break;
+            }
             next_index = (next_index + 1) % self.players.len();
         }
         next_index
     }
 
+    // Removes a player from the game:
+    // - Takes all their unfinished pieces off the board
+    // - Passes the turn on if it was theirs
+    pub fn resign(
+        &mut self,
+        player_index: usize,
+        events: &mut Vec<GameEvent>,
+    ) -> Result<(), RuleError> {
+        if player_index >= self.players.len() || !self.is_playing(player_index) {
+            return Err(RuleError::CannotResign);
+        }
+
+        for piece in &mut self.players[player_index].pieces {
+            if piece.status == PieceStatus::Active {
+                piece.status = PieceStatus::Home;
+                piece.position = -1;
+            }
+        }
+        self.eliminated.push(player_index);
+        events.push(GameEvent::PlayerEliminated {
+            player: player_index,
+        });
+
+        if self.current_player == player_index {
+            self.pass_turn(PassReason::PlayerResigned, events);
+        }
+        Ok(())
+    }
+
     // Handles piece movement logic including:
     // - Moving active pieces forward
     // - Bringing pieces out of home on rolling 6
     // - Handling winning conditions
-    pub fn move_piece(&mut self, piece_index: usize) {
+    pub fn move_piece(&mut self, piece_index: usize, events: &mut Vec<GameEvent>) {
+        let last_position = self.board.last_position();
+        let moved = PieceRef {
+            player: self.current_player,
+            piece: piece_index as u8,
+        };
         let player = &mut self.players[self.current_player];
         let piece = &mut player.pieces[piece_index];
 
         if piece.status == PieceStatus::Active {
+            let old_position = piece.position;
             let new_position = piece.position + self.dice_roll as i8;
-            if new_position as usize == player.path.len() - 1 {
+            if new_position as usize == last_position {
                 piece.status = PieceStatus::Win;
                 piece.position = new_position;
+                events.push(GameEvent::PieceMoved {
+                    piece: moved,
+                    from: old_position,
+                    to: new_position,
+                });
+                events.push(GameEvent::PieceFinished { piece: moved });
                 if player.pieces.iter().all(|p| p.status == PieceStatus::Win) {
                     self.winners.push(self.current_player);
+                    events.push(GameEvent::PlayerWon {
+                        player: self.current_player,
+                    });
+                    self.pass_turn(PassReason::PlayerWon, events);
                 }
-            } else if (new_position as usize) < player.path.len() {
+            } else if (new_position as usize) < last_position {
                 piece.position = new_position;
-                self.handle_collision(new_position);
+                events.push(GameEvent::PieceMoved {
+                    piece: moved,
+                    from: old_position,
+                    to: new_position,
+                });
+                self.handle_collision(moved, events);
             }
         } else if piece.status == PieceStatus::Home && self.dice_roll == 6 {
             piece.status = PieceStatus::Active;
             piece.position = 0;
-            self.handle_collision(0);
+            events.push(GameEvent::PieceEntered { piece: moved });
+            self.handle_collision(moved, events);
         }
     }
 
     // Handles collisions between pieces:
     // - Sends opponent pieces back home if landed on
     // - Updates turn based on dice roll
-    fn handle_collision(&mut self, new_position: i8) {
+    fn handle_collision(&mut self, moved: PieceRef, events: &mut Vec<GameEvent>) {
         let current_player = self.current_player;
-        let current_position = self.players[current_player].path[new_position as usize];
+        let board = self.board;
+        let new_position = self.players[current_player].pieces[moved.piece as usize].position;
+        let current_position = self.path(current_player)[new_position as usize];
 
         for (i, player) in self.players.iter_mut().enumerate() {
             if i != current_player {
-                for piece in &mut player.pieces {
+                let path = board.path(&player.color);
+                for (j, piece) in player.pieces.iter_mut().enumerate() {
                     if piece.status == PieceStatus::Active
-                        && player.path[piece.position as usize] == current_position
+                        && path[piece.position as usize] == current_position
                     {
                         piece.status = PieceStatus::Home;
                         piece.position = -1;
+                        events.push(GameEvent::PieceCaptured {
+                            by: moved,
+                            victim: PieceRef {
+                                player: i,
+                                piece: j as u8,
+                            },
+                        });
                         return;
                     }
                 }
@@ -148,19 +357,21 @@ impl LudoGameState {
         }
 
         if self.dice_roll != 6 || self.sixes >= 3 {
-            self.current_player = self.get_next_turn();
-            self.sixes = 0;
+            self.pass_turn(PassReason::MoveComplete, events);
         }
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct InitializeGameStateCommit {
+    pub game_id: GameId,
+    pub salted: bool, // state_hash is a salted commitment and later plays are hidden
+    pub board: BoardLayout,
     pub current_player: usize,
     pub dice_roll: u8,
     pub winners: Vec<usize>,
     pub sixes: u8,
-    pub state_hash: Digest,
+    pub state_hash: Digest, // See `LudoGameState::digest`
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -168,54 +379,332 @@ pub struct Play {
     pub current_player: usize,
     pub dice_roll: u8,
     pub piece_index: u8, // Which piece the player chose to move
+    #[serde(default)]
+    pub signature: Option<Vec<u8>>, // Ed25519 signature, see `signing_message`
 }
 
-#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
-pub struct PlayGameCommit {
-    pub old_state: Digest,
-    pub new_state: Digest,
-    pub current_player: usize,
-    pub dice_roll: u8,
-    pub piece_index: u8,
+// Leaving the game; the player is ranked last
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Resign {
+    pub player: usize,
+    #[serde(default)]
+    pub signature: Option<Vec<u8>>, // Ed25519 signature, see `signing_message`
+}
+
+// Anything a player can do on their turn
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+    Play(Play),
+    Resign(Resign),
+}
+
+impl From<Play> for Action {
+    fn from(play: Play) -> Self {
+        Action::Play(play)
+    }
+}
+
+impl From<Resign> for Action {
+    fn from(resign: Resign) -> Self {
+        Action::Resign(resign)
+    }
+}
+
+// Why an action cannot be played on a state
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum RuleError {
+    GameOver,       // The game has already ended
+    InvalidDice,    // Dice outside 1..=6
+    NotPlayersTurn, // The play names someone other than the player to move
+    IllegalPiece,   // The chosen piece cannot move with this roll
+    Unauthorized,   // The player has a key and did not sign the action
+    CannotResign,   // Unknown player, or one already out of the game
 }
 
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            RuleError::GameOver => "The game is already over",
+            RuleError::InvalidDice => "Dice roll must be between 1 and 6",
+            RuleError::NotPlayersTurn => "It is not this player's turn",
+            RuleError::IllegalPiece => "The chosen piece cannot move with this roll",
+            RuleError::Unauthorized => "Action is not signed by the player",
+            RuleError::CannotResign => "Player cannot resign",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RuleError {}
+
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PlayGameParams {
     pub state: LudoGameState,
-    pub play: Play,
+    pub action: Action,
 }
 
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct PlayGameResult {
     pub state: LudoGameState,
     pub next_player: usize,
+    pub events: Vec<GameEvent>, // What happened, in order
 }
 
 impl PlayGameParams {
-    pub fn new(state: LudoGameState, play: Play) -> Self {
-        PlayGameParams { state, play }
+    pub fn new(state: LudoGameState, action: impl Into<Action>) -> Self {
+        PlayGameParams {
+            state,
+            action: action.into(),
+        }
+    }
+
+    // Checks the action may be played on the state. The guest relies on this,
+    // so anything it accepts can be proven.
+    pub fn validate(&self) -> Result<(), RuleError> {
+        let state = &self.state;
+        if state.is_over() {
+            return Err(RuleError::GameOver);
+        }
+        match &self.action {
+            Action::Play(play) => {
+                if !(1..=6).contains(&play.dice_roll) {
+                    return Err(RuleError::InvalidDice);
+                }
+                if play.current_player != state.current_player
+                    || !state.is_playing(state.current_player)
+                {
+                    return Err(RuleError::NotPlayersTurn);
+                }
+                // Only the player to move may roll, even when the roll
+                // just passes the turn
+                if !play.is_authorized(state) {
+                    return Err(RuleError::Unauthorized);
+                }
+                // A roll without legal moves passes, whatever piece is named
+                let legal_moves = state.legal_moves(play.dice_roll);
+                if !legal_moves.is_empty() && !legal_moves.contains(&play.piece_index) {
+                    return Err(RuleError::IllegalPiece);
+                }
+            }
+            Action::Resign(resign) => {
+                if resign.player >= state.players.len() || !state.is_playing(resign.player) {
+                    return Err(RuleError::CannotResign);
+                }
+                // Nobody but the player or the arbiter can resign a player
+                // that signs their actions
+                if !resign.is_authorized(state) {
+                    return Err(RuleError::Unauthorized);
+                }
+            }
+        }
+        Ok(())
     }
 
-    // Processes a single play action:
-    // 1. Rolls the dice
-    // 2. Moves the chosen piece if necessary
-    // 3. Returns the new game state and next player
-    pub fn process(&self) -> PlayGameResult {
-        // TODO: require state.current_player == self.play.current_player
+    // Processes a single action:
+    // 1. Checks it is legal and the game is still going, see `validate`
+    // 2. Rolls the dice and moves the chosen piece if necessary, or
+    //    removes a resigning player from the game
+    // 3. Returns the new game state, next player and the events that occurred
+    pub fn process(&self) -> Result<PlayGameResult, RuleError> {
+        self.validate()?;
         let mut state = self.state.clone();
-        let player_moved = state.roll_dice(self.play.dice_roll);
-        if !player_moved {
-            state.move_piece(self.play.piece_index as usize);
+        let mut events = vec![];
+        match &self.action {
+            Action::Play(play) => {
+                let legal_moves = state.legal_moves(play.dice_roll);
+                let player_moved = state.roll_dice(play.dice_roll, &mut events);
+                if !player_moved {
+                    if legal_moves.is_empty() {
+                        state.pass_turn(PassReason::NoLegalMove, &mut events);
+                    } else {
+                        state.move_piece(play.piece_index as usize, &mut events);
+                    }
+                }
+            }
+            Action::Resign(resign) => state.resign(resign.player, &mut events)?,
         }
 
         state.dice_roll = 0;
+        state.move_number += 1;
         let next_player = state.current_player;
 
-        PlayGameResult { state, next_player }
+        Ok(PlayGameResult {
+            state,
+            next_player,
+            events,
+        })
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct WinnersCommit {
+    pub game_id: GameId,
+    pub move_number: u32,   // Number of actions played before the game ended
+    pub state_hash: Digest, // Hash of the final state, linking it to the last play
+    pub end_condition: EndCondition,
     pub winners: Vec<usize>,
+    pub placements: Vec<Placement>, // Every player, best first
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    fn state() -> LudoGameState {
+        let players = BoardLayout::Classic
+            .colors()
+            .iter()
+            .map(|c| Player::new(format!("{:?}", c), *c, [HOME, HOME, HOME, HOME]))
+            .collect();
+        LudoGameState::new(BoardLayout::Classic, players)
+    }
+
+    const HOME: Piece = Piece {
+        position: -1,
+        status: PieceStatus::Home,
+    };
+
+    #[test]
+    fn legal_moves_follow_dice_and_path_end() {
+        let mut state = state();
+        assert_eq!(state.legal_moves(3), Vec::<u8>::new());
+        assert_eq!(state.legal_moves(6), vec![0, 1, 2, 3]);
+
+        state.players[0].pieces[1] = Piece::new(52, PieceStatus::Active);
+        state.players[0].pieces[2] = Piece::new(10, PieceStatus::Active);
+        assert_eq!(state.legal_moves(4), vec![1, 2]);
+        assert_eq!(state.legal_moves(5), vec![2]);
+
+        state.sixes = 2;
+        assert_eq!(state.legal_moves(6), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn roll_without_legal_move_passes_turn() {
+        let mut state = state();
+        state.players[0].pieces[3] = Piece::new(54, PieceStatus::Active);
+
+        let play = Play {
+            current_player: 0,
+            dice_roll: 5,
+            piece_index: 3,
+            signature: None,
+        };
+        let result = PlayGameParams::new(state.clone(), play).process().unwrap();
+        assert_eq!(result.next_player, 1);
+        assert_eq!(result.state.players[0], state.players[0]);
+        assert_eq!(
+            result.events.last(),
+            Some(&GameEvent::TurnPassed {
+                player: 0,
+                next_player: 1,
+                reason: PassReason::NoLegalMove,
+            })
+        );
+
+        let play = Play {
+            current_player: 0,
+            dice_roll: 2,
+            piece_index: 3,
+            signature: None,
+        };
+        let result = PlayGameParams::new(state, play).process().unwrap();
+        assert_eq!(result.state.players[0].pieces[3].position, 56);
+        assert_eq!(result.state.players[0].pieces[3].status, PieceStatus::Win);
+    }
+
+    #[test]
+    fn process_emits_capture_events() {
+        let mut state = state();
+        state.players[0].pieces[0] = Piece::new(10, PieceStatus::Active);
+        state.players[0].pieces[1] = Piece::new(20, PieceStatus::Active);
+        // Cell 28 is Green's position 9 and Red's position 22
+        state.players[1].pieces[2] = Piece::new(9, PieceStatus::Active);
+
+        let play = Play {
+            current_player: 0,
+            dice_roll: 2,
+            piece_index: 1,
+            signature: None,
+        };
+        let result = PlayGameParams::new(state, play).process().unwrap();
+        let red = |piece| PieceRef { player: 0, piece };
+        assert_eq!(
+            result.events,
+            vec![
+                GameEvent::DiceRolled {
+                    player: 0,
+                    value: 2
+                },
+                GameEvent::PieceMoved {
+                    piece: red(1),
+                    from: 20,
+                    to: 22
+                },
+                GameEvent::PieceCaptured {
+                    by: red(1),
+                    victim: PieceRef {
+                        player: 1,
+                        piece: 2
+                    },
+                },
+            ]
+        );
+        // Capturing earns another roll
+        assert_eq!(result.next_player, 0);
+    }
+
+    #[test]
+    fn illegal_actions_are_rejected() {
+        let mut state = state();
+        state.players[0].pieces[1] = Piece::new(10, PieceStatus::Active);
+        let play = |current_player, dice_roll, piece_index| {
+            Action::Play(Play {
+                current_player,
+                dice_roll,
+                piece_index,
+                signature: None,
+            })
+        };
+        let check = |action: Action| {
+            PlayGameParams::new(state.clone(), action)
+                .process()
+                .map(|result| result.next_player)
+        };
+
+        assert_eq!(check(play(0, 0, 1)), Err(RuleError::InvalidDice));
+        assert_eq!(check(play(0, 7, 1)), Err(RuleError::InvalidDice));
+        assert_eq!(check(play(1, 3, 1)), Err(RuleError::NotPlayersTurn));
+        // Yard pieces need a six, and there are only four pieces
+        assert_eq!(check(play(0, 3, 0)), Err(RuleError::IllegalPiece));
+        assert_eq!(check(play(0, 6, 4)), Err(RuleError::IllegalPiece));
+        assert_eq!(
+            check(Action::Resign(Resign {
+                player: 4,
+                signature: None,
+            })),
+            Err(RuleError::CannotResign)
+        );
+
+        assert_eq!(check(play(0, 3, 1)), Ok(1));
+
+        // A keyed player's turn cannot be passed without their signature,
+        // even by a roll that moves nothing
+        let mut keyed = state.clone();
+        keyed.players[0].pieces[1] = HOME;
+        keyed.players[0].public_key = Some([1; 32]);
+        assert!(keyed.legal_moves(5).is_empty());
+        assert_eq!(
+            PlayGameParams::new(keyed, play(0, 5, 0)).validate(),
+            Err(RuleError::Unauthorized)
+        );
+
+        // Nothing can be played once the game has ended
+        state.winners = vec![1, 2, 3];
+        assert_eq!(
+            PlayGameParams::new(state, play(0, 3, 1)).validate(),
+            Err(RuleError::GameOver)
+        );
+    }
 }
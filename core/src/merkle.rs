@@ -0,0 +1,287 @@
+use crate::digest::{finalize_root, sha256, Digest};
+use crate::{
+    BoardLayout, Color, EndCondition, GameId, LudoGameState, Piece, PieceStatus, PublicKey,
+};
+use alloc::{string::String, vec, vec::Vec};
+use serde::{Deserialize, Serialize};
+
+// Domain prefixes keep leaf hashes and inner node hashes apart
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+// Leaf 0 of the state tree: everything except the players' pieces
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MetadataLeaf {
+    pub game_id: GameId,
+    pub board: BoardLayout,
+    pub names: Vec<String>,
+    pub current_player: usize,
+    pub dice_roll: u8,
+    pub winners: Vec<usize>,
+    pub sixes: u8,
+    pub end_condition: EndCondition,
+    pub eliminated: Vec<usize>,
+    pub move_number: u32,
+    pub arbiter: Option<PublicKey>,
+}
+
+// Leaf `player + 1` of the state tree. Board and color are repeated here so a
+// leaf can be checked against the board geometry on its own. The key is part
+// of the leaf so a commitment also fixes who may sign for the player.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PlayerLeaf {
+    pub player: usize,
+    pub board: BoardLayout,
+    pub color: Color,
+    pub pieces: [Piece; 4],
+    pub public_key: Option<PublicKey>,
+}
+
+// Sibling hashes from a leaf up to the root
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub leaf_index: usize,
+    pub siblings: Vec<Digest>,
+}
+
+// A fact about a single player that can be proven against a state root
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Statement {
+    AllPiecesWon { player: usize },
+    PieceAtCell { player: usize, piece: u8, cell: u8 },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DisclosureParams {
+    pub state_root: Digest, // See `LudoGameState::digest`
+    pub leaf: PlayerLeaf,
+    pub proof: MerkleProof,
+    pub statement: Statement,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DisclosureCommit {
+    pub state_root: Digest,
+    pub statement: Statement, // Holds in the state committed to by state_root
+}
+
+// Leaves are hashed over their canonical encoding, see `digest`
+fn hash_leaf(encoded: Vec<u8>) -> Digest {
+    let mut bytes = vec![LEAF_PREFIX];
+    bytes.extend(encoded);
+    sha256(&bytes)
+}
+
+fn hash_node(left: &Digest, right: &Digest) -> Digest {
+    let mut bytes = vec![NODE_PREFIX];
+    bytes.extend_from_slice(left.as_bytes());
+    bytes.extend_from_slice(right.as_bytes());
+    sha256(&bytes)
+}
+
+// Builds every level of the tree, leaves first, padding with zero digests
+fn tree_levels(leaves: Vec<Digest>) -> Vec<Vec<Digest>> {
+    let mut level = leaves;
+    level.resize(level.len().next_power_of_two(), Digest::ZERO);
+    let mut levels = vec![level];
+    while levels.last().unwrap().len() > 1 {
+        let next = levels
+            .last()
+            .unwrap()
+            .chunks(2)
+            .map(|pair| hash_node(&pair[0], &pair[1]))
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+impl PlayerLeaf {
+    pub fn hash(&self) -> Digest {
+        hash_leaf(self.encode())
+    }
+}
+
+impl MerkleProof {
+    // Returns true if the leaf hash sits at `leaf_index` of the tree of the
+    // state with this digest
+    pub fn verify(&self, root: &Digest, leaf_hash: Digest) -> bool {
+        let mut index = self.leaf_index;
+        let mut hash = leaf_hash;
+        for sibling in &self.siblings {
+            hash = match index % 2 {
+                0 => hash_node(&hash, sibling),
+                _ => hash_node(sibling, &hash),
+            };
+            index /= 2;
+        }
+        finalize_root(&hash) == *root
+    }
+}
+
+impl Statement {
+    pub fn player(&self) -> usize {
+        match self {
+            Statement::AllPiecesWon { player } => *player,
+            Statement::PieceAtCell { player, .. } => *player,
+        }
+    }
+
+    // Checks the statement against the disclosed leaf of its player
+    pub fn holds(&self, leaf: &PlayerLeaf) -> bool {
+        if leaf.player != self.player() {
+            return false;
+        }
+        match self {
+            Statement::AllPiecesWon { .. } => {
+                leaf.pieces.iter().all(|p| p.status == PieceStatus::Win)
+            }
+            // Leaves come from the prover, so a piece or position off the
+            // board makes the statement false rather than panicking
+            Statement::PieceAtCell { piece, cell, .. } => {
+                let Some(piece) = leaf.pieces.get(*piece as usize) else {
+                    return false;
+                };
+                piece.status != PieceStatus::Home
+                    && leaf.board.supports(&leaf.color)
+                    && usize::try_from(piece.position)
+                        .ok()
+                        .and_then(|position| leaf.board.path(&leaf.color).get(position))
+                        == Some(cell)
+            }
+        }
+    }
+}
+
+impl DisclosureParams {
+    // Verifies the leaf against the root and the statement against the leaf
+    pub fn verify(&self) -> bool {
+        self.proof.leaf_index == self.leaf.player + 1
+            && self.proof.verify(&self.state_root, self.leaf.hash())
+            && self.statement.holds(&self.leaf)
+    }
+}
+
+impl LudoGameState {
+    pub fn metadata_leaf(&self) -> MetadataLeaf {
+        MetadataLeaf {
+            game_id: self.game_id,
+            board: self.board,
+            names: self.players.iter().map(|p| p.name.clone()).collect(),
+            current_player: self.current_player,
+            dice_roll: self.dice_roll,
+            winners: self.winners.clone(),
+            sixes: self.sixes,
+            end_condition: self.end_condition,
+            eliminated: self.eliminated.clone(),
+            move_number: self.move_number,
+            arbiter: self.arbiter,
+        }
+    }
+
+    pub fn player_leaf(&self, player_index: usize) -> PlayerLeaf {
+        let player = &self.players[player_index];
+        PlayerLeaf {
+            player: player_index,
+            board: self.board,
+            color: player.color,
+            pieces: player.pieces.clone(),
+            public_key: player.public_key,
+        }
+    }
+
+    fn merkle_levels(&self) -> Vec<Vec<Digest>> {
+        let mut leaves = vec![hash_leaf(self.metadata_leaf().encode())];
+        leaves.extend((0..self.players.len()).map(|p| self.player_leaf(p).hash()));
+        tree_levels(leaves)
+    }
+
+    // Top node of the state tree: the metadata leaf followed by one leaf per
+    // player. The state digest is derived from it.
+    pub(crate) fn merkle_top(&self) -> Digest {
+        self.merkle_levels().last().unwrap()[0]
+    }
+
+    // Proof that a player's leaf belongs to this state's tree
+    pub fn merkle_proof(&self, player_index: usize) -> MerkleProof {
+        let leaf_index = player_index + 1;
+        let levels = self.merkle_levels();
+        let siblings = levels[..levels.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(depth, level)| level[(leaf_index >> depth) ^ 1])
+            .collect();
+        MerkleProof {
+            leaf_index,
+            siblings,
+        }
+    }
+
+    // Everything the disclosure guest needs to prove a statement about this state
+    pub fn disclosure(&self, statement: Statement) -> DisclosureParams {
+        let player = statement.player();
+        DisclosureParams {
+            state_root: self.digest(),
+            leaf: self.player_leaf(player),
+            proof: self.merkle_proof(player),
+            statement,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Player;
+    use alloc::format;
+
+    #[test]
+    fn disclosure_proves_statement_against_root() {
+        let home = Piece::new(-1, PieceStatus::Home);
+        let players = BoardLayout::Classic
+            .colors()
+            .iter()
+            .map(|c| Player::new(format!("{:?}", c), *c, [0; 4].map(|_| home.clone())))
+            .collect();
+        let mut state = LudoGameState::new(BoardLayout::Classic, players);
+        // Green's position 24 is cell 70
+        state.players[1].pieces[3] = Piece::new(24, PieceStatus::Active);
+
+        let statement = Statement::PieceAtCell {
+            player: 1,
+            piece: 3,
+            cell: 70,
+        };
+        let params = state.disclosure(statement);
+        assert!(params.verify());
+
+        // Relabelling a leaf as another player's does not match the root
+        let mut forged = params.clone();
+        forged.leaf.player = 2;
+        forged.statement = Statement::PieceAtCell {
+            player: 2,
+            piece: 0,
+            cell: 70,
+        };
+        assert!(!forged.verify());
+
+        let lie = state.disclosure(Statement::AllPiecesWon { player: 1 });
+        assert!(!lie.verify());
+
+        // Pieces and positions off the board make a statement false
+        let leaf = state.player_leaf(1);
+        let at = |piece| Statement::PieceAtCell {
+            player: 1,
+            piece,
+            cell: 70,
+        };
+        assert!(!at(4).holds(&leaf));
+        assert!(!at(255).holds(&leaf));
+        let mut off_path = leaf.clone();
+        off_path.pieces[3].position = 120;
+        assert!(!at(3).holds(&off_path));
+        let mut unseated = leaf;
+        unseated.color = Color::Purple;
+        assert!(!at(3).holds(&unseated));
+    }
+}
@@ -0,0 +1,590 @@
+use crate::{
+    Action, BoardLayout, Color, EndCondition, GameId, GameRecord, LudoGameState, Piece,
+    PieceStatus, Play, PlayGameParams, Player, Resign, RuleError,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+
+// Text notation for a whole game, in the spirit of PGN. Tag pairs describe the
+// game, one per line, followed by the actions:
+//
+//     [Board "Classic"]
+//     [GameId "00000000000000000000000000000000"]
+//     [Rules "FirstFinisher"]
+//     [Red "Alice"]
+//     [Green "Bob"]
+//     [Result "*"]
+//
+//     1. R6:0 R4:0 G3 Rx
+//
+// Seats follow the order of the color tags. A play is the mover's color
+// initial and the dice, then the piece after a colon whenever the roll let a
+// piece move; rolls that pass the turn have no piece. The piece may be left out
+// when only one could move. `Gx` is Green resigning. A number followed by a
+// dot gives the number of the next action and is optional. Signatures are not
+// kept, so replays leave every seat unkeyed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Notation {
+    pub board: BoardLayout,
+    pub game_id: GameId,
+    pub end_condition: EndCondition,
+    pub players: Vec<(Color, String)>, // Seats in turn order
+    pub seed: Option<String>,
+    pub date: Option<String>,
+    pub result: Option<String>, // Finishers in order, or "*" while the game is running
+    pub actions: Vec<Action>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NotationError {
+    pub line: usize, // 1-based
+    pub message: String,
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+// Why a game record has no notation
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecordError {
+    NotInitialPosition, // The record starts from a game already under way
+    Rule(RuleError),    // An action breaks the rules
+}
+
+impl From<RuleError> for RecordError {
+    fn from(error: RuleError) -> Self {
+        RecordError::Rule(error)
+    }
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecordError::NotInitialPosition => {
+                write!(f, "Record does not start from the initial position")
+            }
+            RecordError::Rule(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RecordError {}
+
+// Actions per line of movetext
+const ACTIONS_PER_LINE: usize = 8;
+
+fn initial(color: Color) -> char {
+    match color {
+        Color::Red => 'R',
+        Color::Green => 'G',
+        Color::Blue => 'B',
+        Color::Yellow => 'Y',
+        Color::Purple => 'P',
+        Color::Orange => 'O',
+    }
+}
+
+fn color_name(name: &str) -> Option<Color> {
+    [
+        Color::Red,
+        Color::Green,
+        Color::Blue,
+        Color::Yellow,
+        Color::Purple,
+        Color::Orange,
+    ]
+    .into_iter()
+    .find(|color| format!("{:?}", color) == name)
+}
+
+fn rules_name(end_condition: EndCondition) -> String {
+    match end_condition {
+        EndCondition::FirstFinisher => "FirstFinisher".to_string(),
+        EndCondition::TopN(n) => format!("TopN {}", n),
+        EndCondition::LastPlayerStanding => "LastPlayerStanding".to_string(),
+    }
+}
+
+fn parse_rules(rules: &str) -> Option<EndCondition> {
+    match rules.split_once(' ') {
+        None if rules == "FirstFinisher" => Some(EndCondition::FirstFinisher),
+        None if rules == "LastPlayerStanding" => Some(EndCondition::LastPlayerStanding),
+        Some(("TopN", n)) => n.parse().ok().map(EndCondition::TopN),
+        _ => None,
+    }
+}
+
+fn parse_game_id(hex: &str) -> Option<GameId> {
+    if hex.len() != 32 || !hex.is_ascii() {
+        return None;
+    }
+    let mut id = [0u8; 16];
+    for (i, byte) in id.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(GameId(id))
+}
+
+fn result_of(state: &LudoGameState) -> String {
+    if !state.is_over() {
+        return "*".to_string();
+    }
+    let winners: Vec<String> = state
+        .winners
+        .iter()
+        .map(|&w| format!("{:?}", state.players[w].color))
+        .collect();
+    winners.join(", ")
+}
+
+// Tag values are quoted; quotes and backslashes inside them are escaped
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn parse_tag(line: &str) -> Option<(&str, String)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (name, quoted) = inner.split_once(' ')?;
+    let quoted = quoted.trim().strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.push(chars.next()?),
+            '"' => return None,
+            c => value.push(c),
+        }
+    }
+    Some((name, value))
+}
+
+impl Notation {
+    // Notation for a recorded game, which must start from the initial position.
+    // Keys are not part of the notation, so a keyed game's record is accepted.
+    pub fn from_record(record: &GameRecord) -> Result<Self, RecordError> {
+        let initial_state = &record.initial_state;
+        let mut notation = Notation {
+            board: initial_state.board,
+            game_id: initial_state.game_id,
+            end_condition: initial_state.end_condition,
+            players: initial_state
+                .players
+                .iter()
+                .map(|p| (p.color, p.name.clone()))
+                .collect(),
+            seed: None,
+            date: None,
+            result: None,
+            actions: record.moves.iter().map(|m| m.action.clone()).collect(),
+        };
+        let mut unkeyed = initial_state.clone();
+        unkeyed.arbiter = None;
+        for player in &mut unkeyed.players {
+            player.public_key = None;
+        }
+        if unkeyed != notation.initial_state() {
+            return Err(RecordError::NotInitialPosition);
+        }
+        let states = notation.replay()?;
+        notation.result = Some(result_of(states.last().unwrap()));
+        Ok(notation)
+    }
+
+    pub fn initial_state(&self) -> LudoGameState {
+        let players = self
+            .players
+            .iter()
+            .map(|(color, name)| {
+                Player::new(
+                    name.clone(),
+                    *color,
+                    [0; 4].map(|_| Piece::new(-1, PieceStatus::Home)),
+                )
+            })
+            .collect();
+        LudoGameState::new(self.board, players)
+            .with_game_id(self.game_id)
+            .with_end_condition(self.end_condition)
+    }
+
+    // Every state of the game, from the initial one to the one after the last action
+    pub fn replay(&self) -> Result<Vec<LudoGameState>, RuleError> {
+        let mut states = vec![self.initial_state()];
+        for action in &self.actions {
+            let state = states.last().unwrap().clone();
+            states.push(PlayGameParams::new(state, action.clone()).process()?.state);
+        }
+        Ok(states)
+    }
+
+    // The game as a record, with the events each action produced
+    pub fn to_record(&self) -> Result<GameRecord, RuleError> {
+        let mut state = self.initial_state();
+        let mut record = GameRecord::new(state.clone());
+        for action in &self.actions {
+            let result = PlayGameParams::new(state, action.clone()).process()?;
+            state = result.state;
+            record.record(action.clone(), result.events);
+        }
+        Ok(record)
+    }
+
+    // Reads a game, checking every action is legal where it is played
+    pub fn parse(text: &str) -> Result<Self, NotationError> {
+        let mut tags: Vec<(usize, &str, String)> = vec![];
+        let mut body = vec![];
+        for (i, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('[') && body.is_empty() {
+                let (name, value) = parse_tag(trimmed).ok_or_else(|| NotationError {
+                    line: i + 1,
+                    message: format!("Malformed tag {}", trimmed),
+                })?;
+                tags.push((i + 1, name, value));
+            } else if !trimmed.is_empty() || !body.is_empty() {
+                body.push((i + 1, trimmed));
+            }
+        }
+
+        let mut notation = Notation {
+            board: BoardLayout::Classic,
+            game_id: GameId::default(),
+            end_condition: EndCondition::default(),
+            players: vec![],
+            seed: None,
+            date: None,
+            result: None,
+            actions: vec![],
+        };
+        for (line, name, value) in tags {
+            let invalid = |what: &str| NotationError {
+                line,
+                message: format!("Invalid {} {}", what, value),
+            };
+            match name {
+                "Board" => {
+                    notation.board = match value.as_str() {
+                        "Classic" => BoardLayout::Classic,
+                        "SixPlayer" => BoardLayout::SixPlayer,
+                        _ => return Err(invalid("board")),
+                    }
+                }
+                "GameId" => notation.game_id = parse_game_id(&value).ok_or(invalid("game id"))?,
+                "Rules" => notation.end_condition = parse_rules(&value).ok_or(invalid("rules"))?,
+                "Seed" => notation.seed = Some(value),
+                "Date" => notation.date = Some(value),
+                "Result" => notation.result = Some(value),
+                _ => match color_name(name) {
+                    Some(color) => notation.players.push((color, value)),
+                    None => {
+                        return Err(NotationError {
+                            line,
+                            message: format!("Unknown tag {}", name),
+                        })
+                    }
+                },
+            }
+        }
+
+        let header_error = |message: &str| NotationError {
+            line: 1,
+            message: message.to_string(),
+        };
+        let seats = notation.players.len();
+        if seats < 2 || seats > notation.board.max_players() {
+            return Err(header_error("Unsupported number of players for this board"));
+        }
+        if !notation
+            .players
+            .iter()
+            .all(|(color, _)| notation.board.supports(color))
+        {
+            return Err(header_error("Player color is not seated on this board"));
+        }
+        if (1..seats).any(|i| {
+            notation.players[..i]
+                .iter()
+                .any(|p| p.0 == notation.players[i].0)
+        }) {
+            return Err(header_error("Color is seated twice"));
+        }
+
+        let mut state = notation.initial_state();
+        for (line, text) in body {
+            for token in text.split_whitespace() {
+                let error = |message: String| NotationError { line, message };
+                if let Some(number) = token.strip_suffix('.') {
+                    let expected = notation.actions.len() + 1;
+                    if number.parse::<usize>().ok() != Some(expected) {
+                        return Err(error(format!(
+                            "Expected action number {}, found {}",
+                            expected, token
+                        )));
+                    }
+                    continue;
+                }
+                let action = parse_action(token, &state).map_err(error)?;
+                state = PlayGameParams::new(state, action.clone())
+                    .process()
+                    .map_err(|e| error(e.to_string()))?
+                    .state;
+                notation.actions.push(action);
+            }
+        }
+        Ok(notation)
+    }
+}
+
+// Reads one action and checks it can be played on `state`
+fn parse_action(token: &str, state: &LudoGameState) -> Result<Action, String> {
+    let mut chars = token.chars();
+    let letter = chars.next().unwrap();
+    let player = state
+        .players
+        .iter()
+        .position(|p| initial(p.color) == letter)
+        .ok_or_else(|| format!("No player plays {}", letter))?;
+    if state.is_over() {
+        return Err(format!("{} is played after the game ended", token));
+    }
+
+    let rest = chars.as_str();
+    if rest == "x" {
+        if !state.is_playing(player) {
+            return Err(format!("{} cannot resign", letter));
+        }
+        return Ok(Action::Resign(Resign {
+            player,
+            signature: None,
+        }));
+    }
+    if player != state.current_player {
+        return Err(format!("{} is not {}'s turn", token, letter));
+    }
+
+    let (dice, piece) = match rest.split_once(':') {
+        Some((dice, piece)) => (dice, Some(piece)),
+        None => (rest, None),
+    };
+    let dice_roll = match dice.parse::<u8>() {
+        Ok(dice_roll @ 1..=6) => dice_roll,
+        _ => return Err(format!("Invalid dice in {}", token)),
+    };
+    let legal_moves = state.legal_moves(dice_roll);
+    let piece_index = match (piece, legal_moves.as_slice()) {
+        (None, []) => 0,
+        (None, [only]) => *only,
+        (None, _) => return Err(format!("{} does not say which piece moves", token)),
+        (Some(_), []) => return Err(format!("{} moves a piece, but the roll passes", token)),
+        (Some(piece), legal_moves) => match piece.parse::<u8>() {
+            Ok(piece) if legal_moves.contains(&piece) => piece,
+            _ => return Err(format!("{} moves a piece that cannot move", token)),
+        },
+    };
+    Ok(Action::Play(Play {
+        current_player: player,
+        dice_roll,
+        piece_index,
+        signature: None,
+    }))
+}
+
+impl Notation {
+    // Writes the game out as text. Actions that break the rules have no
+    // notation, so they are refused rather than written.
+    pub fn to_notation(&self) -> Result<String, RuleError> {
+        let states = self.replay()?;
+        let mut text = String::new();
+        let board = match self.board {
+            BoardLayout::Classic => "Classic",
+            BoardLayout::SixPlayer => "SixPlayer",
+        };
+        text += &format!("[Board \"{}\"]\n", board);
+        let game_id: String = self
+            .game_id
+            .0
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        text += &format!("[GameId \"{}\"]\n", game_id);
+        text += &format!("[Rules \"{}\"]\n", rules_name(self.end_condition));
+        if let Some(date) = &self.date {
+            text += &format!("[Date \"{}\"]\n", escape(date));
+        }
+        if let Some(seed) = &self.seed {
+            text += &format!("[Seed \"{}\"]\n", escape(seed));
+        }
+        for (color, name) in &self.players {
+            text += &format!("[{:?} \"{}\"]\n", color, escape(name));
+        }
+        if let Some(result) = &self.result {
+            text += &format!("[Result \"{}\"]\n", escape(result));
+        }
+
+        for (i, action) in self.actions.iter().enumerate() {
+            if i % ACTIONS_PER_LINE == 0 {
+                text += &format!("\n{}.", i + 1);
+            }
+            let state = &states[i];
+            match action {
+                Action::Resign(resign) => {
+                    text += &format!(" {}x", initial(state.players[resign.player].color))
+                }
+                Action::Play(play) => {
+                    let color = initial(state.players[state.current_player].color);
+                    text += &format!(" {}{}", color, play.dice_roll);
+                    if !state.legal_moves(play.dice_roll).is_empty() {
+                        text += &format!(":{}", play.piece_index);
+                    }
+                }
+            }
+        }
+        if !self.actions.is_empty() {
+            text.push('\n');
+        }
+        Ok(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notation() -> Notation {
+        Notation {
+            board: BoardLayout::Classic,
+            game_id: GameId([0xab; 16]),
+            end_condition: EndCondition::FirstFinisher,
+            players: vec![
+                (Color::Red, "Alice \"Ace\"".to_string()),
+                (Color::Green, "Bob".to_string()),
+                (Color::Yellow, "Carol".to_string()),
+            ],
+            seed: Some("42".to_string()),
+            date: Some("2024.05.01".to_string()),
+            result: None,
+            actions: vec![],
+        }
+    }
+
+    // Plays a whole game with dice from a small generator, moving the last
+    // legal piece, and one resignation along the way
+    fn played() -> Notation {
+        let mut notation = notation();
+        let mut state = notation.initial_state();
+        let mut seed: u32 = 7;
+        let mut record = GameRecord::new(state.clone());
+        while !state.is_over() {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let dice_roll = (seed >> 16) as u8 % 6 + 1;
+            let action = if record.moves.len() == 40 {
+                Action::Resign(Resign {
+                    player: 2,
+                    signature: None,
+                })
+            } else {
+                Action::Play(Play {
+                    current_player: state.current_player,
+                    dice_roll,
+                    piece_index: state.legal_moves(dice_roll).last().copied().unwrap_or(0),
+                    signature: None,
+                })
+            };
+            let result = PlayGameParams::new(state, action.clone())
+                .process()
+                .unwrap();
+            state = result.state;
+            record.record(action, result.events);
+        }
+        notation.actions = record.moves.iter().map(|m| m.action.clone()).collect();
+        notation.result = Notation::from_record(&record).unwrap().result;
+        notation
+    }
+
+    #[test]
+    fn notation_round_trips_and_replays() {
+        let notation = played();
+        let text = notation.to_notation().unwrap();
+        assert!(text.starts_with("[Board \"Classic\"]\n"));
+        assert!(text.contains("[Red \"Alice \\\"Ace\\\"\"]"));
+        assert!(text.contains("\n1. R"));
+        assert!(text.contains(" Yx"));
+
+        let parsed = Notation::parse(&text).unwrap();
+        assert_eq!(parsed, notation);
+        assert_eq!(parsed.to_notation().unwrap(), text);
+
+        // Records carry no seed or date
+        let from_record = Notation::from_record(&parsed.to_record().unwrap()).unwrap();
+        assert_eq!(from_record.actions, notation.actions);
+        assert_eq!(from_record.result, notation.result);
+
+        let states = parsed.replay().unwrap();
+        assert_eq!(states.len(), notation.actions.len() + 1);
+        assert_eq!(states[0], notation.initial_state());
+        assert!(states.last().unwrap().is_over());
+        assert_ne!(parsed.result.as_deref(), Some("*"));
+
+        // Games that break the rules, or records of a game already under
+        // way, have no notation
+        let mut illegal = notation.clone();
+        illegal.actions.push(illegal.actions[0].clone());
+        assert_eq!(illegal.to_notation(), Err(RuleError::GameOver));
+        let mut record = parsed.to_record().unwrap();
+        record.initial_state = states[1].clone();
+        assert_eq!(
+            Notation::from_record(&record),
+            Err(RecordError::NotInitialPosition)
+        );
+    }
+
+    #[test]
+    fn malformed_notation_is_rejected() {
+        let header = "[Board \"Classic\"]\n[Red \"A\"]\n[Green \"B\"]\n\n";
+        let error = |text: &str| Notation::parse(text).unwrap_err();
+
+        assert_eq!(Notation::parse(header).unwrap().actions, vec![]);
+        assert_eq!(error("[Board Classic]").line, 1);
+        assert_eq!(error("[Board \"Round\"]").message, "Invalid board Round");
+        assert_eq!(error("[Red \"A\"]").line, 1);
+        assert_eq!(
+            error("[Red \"A\"]\n[Red \"B\"]").message,
+            "Color is seated twice"
+        );
+        assert_eq!(
+            error("[Board \"Classic\"]\n[Purple \"A\"]\n[Red \"B\"]").line,
+            1
+        );
+        assert_eq!(error("[Rules \"TopN x\"]").message, "Invalid rules TopN x");
+        assert_eq!(error("[Turn \"1\"]").message, "Unknown tag Turn");
+
+        let moves = |body: &str| error(&format!("{}{}", header, body));
+        assert_eq!(moves("1. R6:0\nG3 B3").line, 6);
+        assert_eq!(moves("1. R6:0 G3").message, "G3 is not G's turn");
+        assert_eq!(moves("R7").message, "Invalid dice in R7");
+        assert_eq!(moves("R6").message, "R6 does not say which piece moves");
+        assert_eq!(
+            moves("R3:0").message,
+            "R3:0 moves a piece, but the roll passes"
+        );
+        assert_eq!(moves("R6:4").message, "R6:4 moves a piece that cannot move");
+        assert_eq!(
+            moves("2. R6:0").message,
+            "Expected action number 1, found 2."
+        );
+        assert_eq!(moves("Rx Rx").message, "Rx is played after the game ended");
+
+        let parsed = Notation::parse(&format!("{}1. R6:0 R4:0 G3 R2", header)).unwrap();
+        assert_eq!(parsed.actions.len(), 4);
+        assert_eq!(parsed.replay().unwrap()[4].current_player, 1);
+    }
+}
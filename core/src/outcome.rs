@@ -0,0 +1,213 @@
+use crate::{LudoGameState, PieceStatus};
+use alloc::{format, string::String, vec::Vec};
+use core::str::FromStr;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum EndCondition {
+    FirstFinisher, // The game ends as soon as one player brings all pieces home
+    TopN(u8),      // The game ends once this many players have finished
+    #[default]
+    LastPlayerStanding, // Play until only one player is left on the board
+}
+
+impl EndCondition {
+    // Number of finishers needed to end a game with `player_count` players
+    pub fn required_winners(&self, player_count: usize) -> usize {
+        let all_but_one = player_count.saturating_sub(1).max(1);
+        match self {
+            EndCondition::FirstFinisher => 1,
+            EndCondition::TopN(n) => (*n as usize).clamp(1, all_but_one),
+            EndCondition::LastPlayerStanding => all_but_one,
+        }
+    }
+}
+
+// Command line names: `first`, `last`, or `top:N`
+impl FromStr for EndCondition {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.split_once(':') {
+            None if name == "first" => Ok(EndCondition::FirstFinisher),
+            None if name == "last" => Ok(EndCondition::LastPlayerStanding),
+            Some(("top", n)) => n
+                .parse()
+                .map(EndCondition::TopN)
+                .map_err(|_| format!("Invalid number of winners {}", n)),
+            _ => Err(format!("Unknown end condition {}", name)),
+        }
+    }
+}
+
+// Final standing of a single player. Players that share a rank tied on both
+// pieces finished and progress.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Placement {
+    pub player: usize,
+    pub rank: usize,         // 1 for first place
+    pub pieces_finished: u8, // Pieces that reached the win cell
+    pub progress: u16,       // Total steps travelled by all pieces
+    pub eliminated: bool,    // Resigned or forfeited before the end
+}
+
+impl LudoGameState {
+    // Returns true once enough players have finished for the end condition,
+    // or when resignations leave at most one player on the board
+    pub fn is_over(&self) -> bool {
+        let playing = (0..self.players.len())
+            .filter(|&p| self.is_playing(p))
+            .count();
+        playing <= 1
+            || self.winners.len() >= self.end_condition.required_winners(self.players.len())
+    }
+
+    // Total steps travelled by a player's pieces; pieces in the yard count as zero
+    pub fn progress(&self, player_index: usize) -> u16 {
+        self.players[player_index]
+            .pieces
+            .iter()
+            .map(|piece| match piece.status {
+                PieceStatus::Home => 0,
+                _ => piece.position as u16 + 1,
+            })
+            .sum()
+    }
+
+    // Ranks every player: finishers in the order they finished, followed by the
+    // remaining players ordered by pieces finished and then by progress, and
+    // finally eliminated players, the most recently eliminated first
+    pub fn placements(&self) -> Vec<Placement> {
+        let placement = |player: usize| Placement {
+            player,
+            rank: 0,
+            pieces_finished: self.players[player]
+                .pieces
+                .iter()
+                .filter(|p| p.status == PieceStatus::Win)
+                .count() as u8,
+            progress: self.progress(player),
+            eliminated: self.eliminated.contains(&player),
+        };
+
+        let mut placements: Vec<Placement> = self.winners.iter().map(|&p| placement(p)).collect();
+        for (rank, p) in placements.iter_mut().enumerate() {
+            p.rank = rank + 1;
+        }
+
+        let mut unfinished: Vec<Placement> = (0..self.players.len())
+            .filter(|&p| self.is_playing(p))
+            .map(placement)
+            .collect();
+        unfinished.sort_by(|a, b| {
+            (b.pieces_finished, b.progress)
+                .cmp(&(a.pieces_finished, a.progress))
+                .then(a.player.cmp(&b.player))
+        });
+
+        for mut p in unfinished {
+            p.rank = match placements.last() {
+                Some(prev)
+                    if !self.winners.contains(&prev.player)
+                        && (prev.pieces_finished, prev.progress)
+                            == (p.pieces_finished, p.progress) =>
+                {
+                    prev.rank
+                }
+                _ => placements.len() + 1,
+            };
+            placements.push(p);
+        }
+
+        for &player in self.eliminated.iter().rev() {
+            let mut p = placement(player);
+            p.rank = placements.len() + 1;
+            placements.push(p);
+        }
+
+        placements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoardLayout, Piece, Player};
+    use alloc::{format, vec};
+
+    fn state() -> LudoGameState {
+        let pieces = [
+            Piece::new(-1, PieceStatus::Home),
+            Piece::new(-1, PieceStatus::Home),
+            Piece::new(-1, PieceStatus::Home),
+            Piece::new(-1, PieceStatus::Home),
+        ];
+        let players = BoardLayout::Classic
+            .colors()
+            .iter()
+            .map(|c| Player::new(format!("{:?}", c), *c, pieces.clone()))
+            .collect();
+        LudoGameState::new(BoardLayout::Classic, players)
+    }
+
+    #[test]
+    fn end_condition_counts_required_winners() {
+        let mut state = state().with_end_condition(EndCondition::TopN(2));
+        state.winners = vec![3];
+        assert!(!state.is_over());
+        state.winners.push(1);
+        assert!(state.is_over());
+
+        assert_eq!(EndCondition::FirstFinisher.required_winners(4), 1);
+        assert_eq!(EndCondition::TopN(9).required_winners(4), 3);
+        assert_eq!(EndCondition::LastPlayerStanding.required_winners(6), 5);
+
+        assert_eq!("top:2".parse(), Ok(EndCondition::TopN(2)));
+        assert_eq!("last".parse(), Ok(EndCondition::LastPlayerStanding));
+        assert!("top:x".parse::<EndCondition>().is_err());
+    }
+
+    #[test]
+    fn placements_rank_unfinished_by_pieces_finished_then_progress() {
+        let mut state = state();
+        state.winners = vec![2];
+        for piece in state.players[2].pieces.iter_mut() {
+            *piece = Piece::new(56, PieceStatus::Win);
+        }
+        state.players[0].pieces[0] = Piece::new(56, PieceStatus::Win);
+        state.players[1].pieces[0] = Piece::new(40, PieceStatus::Active);
+        state.players[3].pieces[0] = Piece::new(40, PieceStatus::Active);
+
+        let ranking: Vec<(usize, usize)> = state
+            .placements()
+            .iter()
+            .map(|p| (p.player, p.rank))
+            .collect();
+        assert_eq!(ranking, vec![(2, 1), (0, 2), (1, 3), (3, 3)]);
+    }
+
+    #[test]
+    fn eliminated_players_are_ranked_last() {
+        let mut state = state();
+        state.players[1].pieces[0] = Piece::new(40, PieceStatus::Active);
+        state.resign(1, &mut vec![]).unwrap();
+        state.resign(0, &mut vec![]).unwrap();
+        assert_eq!(state.current_player, 2);
+        assert_eq!(
+            state.players[1].pieces[0],
+            Piece::new(-1, PieceStatus::Home)
+        );
+        assert!(!state.is_over());
+
+        let ranking: Vec<(usize, usize)> = state
+            .placements()
+            .iter()
+            .map(|p| (p.player, p.rank))
+            .collect();
+        assert_eq!(ranking, vec![(2, 1), (3, 1), (0, 3), (1, 4)]);
+
+        state.resign(3, &mut vec![]).unwrap();
+        assert!(state.is_over());
+        assert_eq!(state.get_next_turn(), 2);
+    }
+}
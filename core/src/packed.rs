@@ -0,0 +1,327 @@
+use crate::{
+    BoardLayout, Color, EndCondition, GameId, LudoGameState, Piece, PieceStatus, Player, PublicKey,
+};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+// Seats of the largest board
+const MAX_PLAYERS: usize = 6;
+
+pub const PACKED_STATE_LEN: usize = 72;
+pub const PACKED_STATE_WORDS: usize = PACKED_STATE_LEN / 4;
+
+// Marks an unused slot in the winner and elimination lists
+const EMPTY: u8 = 0xff;
+
+// Byte offsets of the packed layout
+const GAME_ID: usize = 0; // 16 bytes
+const BOARD: usize = 16;
+const PLAYER_COUNT: usize = 17;
+const CURRENT_PLAYER: usize = 18;
+const DICE_ROLL: usize = 19;
+const SIXES: usize = 20;
+const END_CONDITION: usize = 21; // Tag, then the TopN count
+const WINNERS: usize = 23; // Player indices in finishing order
+const ELIMINATED: usize = 29; // Player indices in elimination order
+const POSITIONS: usize = 35; // One byte per piece, 4 per seat
+const STATUSES: usize = 59; // Two bits per piece, one byte per seat
+const MOVE_NUMBER: usize = 65; // Little-endian u32, then 3 bytes of padding
+
+pub(crate) fn board_code(board: BoardLayout) -> u8 {
+    match board {
+        BoardLayout::Classic => 0,
+        BoardLayout::SixPlayer => 1,
+    }
+}
+
+fn board_from_code(code: u8) -> Result<BoardLayout, PackedError> {
+    match code {
+        0 => Ok(BoardLayout::Classic),
+        1 => Ok(BoardLayout::SixPlayer),
+        _ => Err(PackedError::UnknownBoard(code)),
+    }
+}
+
+pub(crate) fn color_code(color: Color) -> u8 {
+    match color {
+        Color::Red => 0,
+        Color::Green => 1,
+        Color::Blue => 2,
+        Color::Yellow => 3,
+        Color::Purple => 4,
+        Color::Orange => 5,
+    }
+}
+
+pub(crate) fn status_code(status: &PieceStatus) -> u8 {
+    match status {
+        PieceStatus::Home => 0,
+        PieceStatus::Active => 1,
+        PieceStatus::Win => 2,
+    }
+}
+
+fn status_from_code(code: u8) -> Result<PieceStatus, PackedError> {
+    match code {
+        0 => Ok(PieceStatus::Home),
+        1 => Ok(PieceStatus::Active),
+        2 => Ok(PieceStatus::Win),
+        _ => Err(PackedError::UnknownStatus(code)),
+    }
+}
+
+pub(crate) fn end_condition_code(end_condition: EndCondition) -> [u8; 2] {
+    match end_condition {
+        EndCondition::FirstFinisher => [0, 0],
+        EndCondition::TopN(n) => [1, n],
+        EndCondition::LastPlayerStanding => [2, 0],
+    }
+}
+
+fn end_condition_from_code(code: [u8; 2]) -> Result<EndCondition, PackedError> {
+    match code {
+        [0, _] => Ok(EndCondition::FirstFinisher),
+        [1, n] => Ok(EndCondition::TopN(n)),
+        [2, _] => Ok(EndCondition::LastPlayerStanding),
+        _ => Err(PackedError::UnknownEndCondition(code[0])),
+    }
+}
+
+// Why packed bytes do not describe a state
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PackedError {
+    UnknownBoard(u8),        // Board code with no layout
+    UnknownStatus(u8),       // Piece status code with no status
+    UnknownEndCondition(u8), // End condition tag with no condition
+    RosterMismatch,          // The roster has another number of players
+    InvalidPlayer(u8),       // A player index past the last seat
+    UnseatedColor(Color),    // A roster color the board has no seat for
+    InvalidPosition(i8),     // A piece position off its path
+}
+
+impl fmt::Display for PackedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PackedError::UnknownBoard(code) => write!(f, "Unknown board code {}", code),
+            PackedError::UnknownStatus(code) => write!(f, "Unknown piece status {}", code),
+            PackedError::UnknownEndCondition(code) => write!(f, "Unknown end condition {}", code),
+            PackedError::RosterMismatch => write!(f, "Roster is for another game"),
+            PackedError::InvalidPlayer(player) => write!(f, "No player at seat {}", player),
+            PackedError::UnseatedColor(color) => {
+                write!(f, "{:?} is not seated on this board", color)
+            }
+            PackedError::InvalidPosition(position) => {
+                write!(f, "Piece position {} is off the path", position)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PackedError {}
+
+// The parts of a state that never change during a game. Guests read it once per
+// proof next to the packed state; it is not needed to follow the game itself.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Roster {
+    pub names: Vec<String>,
+    pub colors: Vec<Color>,
+    pub public_keys: Vec<Option<PublicKey>>,
+    pub arbiter: Option<PublicKey>,
+}
+
+impl Roster {
+    pub fn new(state: &LudoGameState) -> Self {
+        Roster {
+            names: state.players.iter().map(|p| p.name.clone()).collect(),
+            colors: state.players.iter().map(|p| p.color).collect(),
+            public_keys: state.players.iter().map(|p| p.public_key).collect(),
+            arbiter: state.arbiter,
+        }
+    }
+}
+
+// Everything in a state that changes during a game, in a fixed 18-word layout.
+// Winners are stored in finishing order rather than as a bitmask because the
+// order decides the placements. This is only how states travel to and from the
+// guests: they still rebuild the full state and hash it for the journals.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PackedState(pub [u8; PACKED_STATE_LEN]);
+
+impl PackedState {
+    pub fn new(state: &LudoGameState) -> Self {
+        assert!(
+            state.players.len() <= MAX_PLAYERS,
+            "Too many players to pack"
+        );
+        let mut bytes = [0u8; PACKED_STATE_LEN];
+        bytes[GAME_ID..GAME_ID + 16].copy_from_slice(&state.game_id.0);
+        bytes[BOARD] = board_code(state.board);
+        bytes[PLAYER_COUNT] = state.players.len() as u8;
+        bytes[CURRENT_PLAYER] = state.current_player as u8;
+        bytes[DICE_ROLL] = state.dice_roll;
+        bytes[SIXES] = state.sixes;
+        bytes[END_CONDITION..END_CONDITION + 2]
+            .copy_from_slice(&end_condition_code(state.end_condition));
+        bytes[WINNERS..WINNERS + MAX_PLAYERS].fill(EMPTY);
+        for (slot, &player) in state.winners.iter().enumerate() {
+            bytes[WINNERS + slot] = player as u8;
+        }
+        bytes[ELIMINATED..ELIMINATED + MAX_PLAYERS].fill(EMPTY);
+        for (slot, &player) in state.eliminated.iter().enumerate() {
+            bytes[ELIMINATED + slot] = player as u8;
+        }
+        for (seat, player) in state.players.iter().enumerate() {
+            for (i, piece) in player.pieces.iter().enumerate() {
+                bytes[POSITIONS + 4 * seat + i] = piece.position as u8;
+                bytes[STATUSES + seat] |= status_code(&piece.status) << (2 * i);
+            }
+        }
+        bytes[MOVE_NUMBER..MOVE_NUMBER + 4].copy_from_slice(&state.move_number.to_le_bytes());
+        PackedState(bytes)
+    }
+
+    // Rebuilds the full state; `roster` must come from the same game. The bytes
+    // come from outside the guest, so every code and index is checked.
+    pub fn unpack(&self, roster: &Roster) -> Result<LudoGameState, PackedError> {
+        let bytes = &self.0;
+        let player_count = bytes[PLAYER_COUNT] as usize;
+        if player_count > MAX_PLAYERS
+            || roster.names.len() != player_count
+            || roster.colors.len() != player_count
+            || roster.public_keys.len() != player_count
+        {
+            return Err(PackedError::RosterMismatch);
+        }
+        let board = board_from_code(bytes[BOARD])?;
+        if let Some(color) = roster.colors.iter().find(|c| !board.supports(c)) {
+            return Err(PackedError::UnseatedColor(*color));
+        }
+        let player = |code: u8| match (code as usize) < player_count {
+            true => Ok(code as usize),
+            false => Err(PackedError::InvalidPlayer(code)),
+        };
+
+        let players = (0..player_count)
+            .map(|seat| {
+                let mut pieces = [0; 4].map(|_| Piece::new(-1, PieceStatus::Home));
+                for (i, piece) in pieces.iter_mut().enumerate() {
+                    piece.position = bytes[POSITIONS + 4 * seat + i] as i8;
+                    if !(-1..=board.last_position() as i8).contains(&piece.position) {
+                        return Err(PackedError::InvalidPosition(piece.position));
+                    }
+                    piece.status = status_from_code((bytes[STATUSES + seat] >> (2 * i)) & 0b11)?;
+                }
+                Ok(Player {
+                    name: roster.names[seat].clone(),
+                    color: roster.colors[seat],
+                    pieces,
+                    public_key: roster.public_keys[seat],
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let list = |start: usize| -> Result<Vec<usize>, PackedError> {
+            bytes[start..start + MAX_PLAYERS]
+                .iter()
+                .take_while(|&&p| p != EMPTY)
+                .map(|&p| player(p))
+                .collect()
+        };
+
+        Ok(LudoGameState {
+            game_id: GameId(bytes[GAME_ID..GAME_ID + 16].try_into().unwrap()),
+            board,
+            players,
+            current_player: player(bytes[CURRENT_PLAYER])?,
+            dice_roll: bytes[DICE_ROLL],
+            winners: list(WINNERS)?,
+            sixes: bytes[SIXES],
+            end_condition: end_condition_from_code([
+                bytes[END_CONDITION],
+                bytes[END_CONDITION + 1],
+            ])?,
+            eliminated: list(ELIMINATED)?,
+            move_number: u32::from_le_bytes(
+                bytes[MOVE_NUMBER..MOVE_NUMBER + 4].try_into().unwrap(),
+            ),
+            arbiter: roster.arbiter,
+        })
+    }
+
+    // Word layout the guests read and write with `read_slice` and `write_slice`
+    pub fn to_words(&self) -> [u32; PACKED_STATE_WORDS] {
+        core::array::from_fn(|i| u32::from_le_bytes(self.0[4 * i..4 * i + 4].try_into().unwrap()))
+    }
+
+    pub fn from_words(words: &[u32; PACKED_STATE_WORDS]) -> Self {
+        let mut bytes = [0u8; PACKED_STATE_LEN];
+        for (i, word) in words.iter().enumerate() {
+            bytes[4 * i..4 * i + 4].copy_from_slice(&word.to_le_bytes());
+        }
+        PackedState(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{format, vec};
+
+    #[test]
+    fn packing_round_trips_every_field() {
+        let board = BoardLayout::SixPlayer;
+        let players = board
+            .colors()
+            .iter()
+            .map(|c| {
+                Player::new(
+                    format!("{:?}", c),
+                    *c,
+                    [0; 4].map(|_| Piece::new(-1, PieceStatus::Home)),
+                )
+            })
+            .collect();
+        let mut state = LudoGameState::new(board, players)
+            .with_game_id(GameId([0xab; 16]))
+            .with_end_condition(EndCondition::TopN(3));
+        state.players[1] = state.players[1].clone().with_public_key([4; 32]);
+        state.arbiter = Some([7; 32]);
+        state.players[2].pieces[1] = Piece::new(80, PieceStatus::Active);
+        state.players[5].pieces = [0; 4].map(|_| Piece::new(82, PieceStatus::Win));
+        state.winners = vec![5];
+        state.eliminated = vec![3, 0];
+        state.current_player = 4;
+        state.dice_roll = 6;
+        state.sixes = 2;
+        state.move_number = 300;
+
+        let packed = PackedState::new(&state);
+        let roster = Roster::new(&state);
+        assert_eq!(packed.unpack(&roster), Ok(state));
+        assert_eq!(PackedState::from_words(&packed.to_words()), packed);
+
+        // Unknown codes and out of range players are refused
+        let mut bad = packed;
+        bad.0[BOARD] = 7;
+        assert_eq!(bad.unpack(&roster), Err(PackedError::UnknownBoard(7)));
+        let mut bad = packed;
+        bad.0[STATUSES] = 0b11;
+        assert_eq!(bad.unpack(&roster), Err(PackedError::UnknownStatus(3)));
+        let mut bad = packed;
+        bad.0[CURRENT_PLAYER] = 6;
+        assert_eq!(bad.unpack(&roster), Err(PackedError::InvalidPlayer(6)));
+        let mut bad = packed;
+        bad.0[PLAYER_COUNT] = 4;
+        assert_eq!(bad.unpack(&roster), Err(PackedError::RosterMismatch));
+        let mut bad = packed;
+        bad.0[POSITIONS] = 83;
+        assert_eq!(bad.unpack(&roster), Err(PackedError::InvalidPosition(83)));
+        let mut bad = packed;
+        bad.0[BOARD] = board_code(BoardLayout::Classic);
+        assert_eq!(
+            bad.unpack(&roster),
+            Err(PackedError::UnseatedColor(Color::Purple))
+        );
+    }
+}
@@ -0,0 +1,393 @@
+use crate::board::WIN;
+use crate::{BoardLayout, Color, LudoGameState, PieceStatus};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+
+// Squares per side of the classic board
+pub const GRID_SIZE: u8 = 15;
+
+// A square of the classic 15x15 grid, counted from the top left
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct GridPos {
+    pub row: u8,
+    pub col: u8,
+}
+
+impl GridPos {
+    const fn new(row: u8, col: u8) -> Self {
+        GridPos { row, col }
+    }
+}
+
+// Every square a classic board needs, for drawing it without knowing the cell
+// numbering in `board`
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BoardGeometry {
+    pub size: u8,                          // Squares per side
+    pub cells: Vec<GridPos>,               // Indexed by cell id
+    pub yards: Vec<(Color, [GridPos; 4])>, // Where each color's pieces wait, by piece index
+    pub finish: Vec<(Color, GridPos)>,     // Centre square each color's finished pieces go to
+    pub win: GridPos,                      // The centre of the board, for the win cell
+}
+
+impl BoardGeometry {
+    pub fn classic() -> Self {
+        let colors = BoardLayout::Classic.colors();
+        BoardGeometry {
+            size: GRID_SIZE,
+            cells: (0..BoardLayout::Classic.cell_count() as u8)
+                .map(|cell| cell_position(cell).unwrap())
+                .collect(),
+            yards: colors
+                .iter()
+                .map(|&color| {
+                    (
+                        color,
+                        [0, 1, 2, 3].map(|i| yard_position(color, i).unwrap()),
+                    )
+                })
+                .collect(),
+            finish: colors
+                .iter()
+                .map(|&color| (color, finish_position(color).unwrap()))
+                .collect(),
+            win: CENTRE,
+        }
+    }
+}
+
+const CENTRE: GridPos = GridPos::new(7, 7);
+
+// Square of a classic board cell, or of the centre for the win cell. The
+// numbering runs row by row, see `board`.
+pub fn cell_position(cell: u8) -> Option<GridPos> {
+    let (row, col) = match cell {
+        0..=17 => (cell / 3, 6 + cell % 3),
+        18..=53 => {
+            let k = cell - 18;
+            let col = k % 12;
+            (6 + k / 12, if col < 6 { col } else { col + 3 })
+        }
+        54..=71 => {
+            let k = cell - 54;
+            (9 + k / 3, 6 + k % 3)
+        }
+        WIN => return Some(CENTRE),
+        _ => return None,
+    };
+    Some(GridPos::new(row, col))
+}
+
+// Top-left corner of each color's 6x6 home yard
+fn yard_corner(color: Color) -> Option<GridPos> {
+    match color {
+        Color::Red => Some(GridPos::new(0, 0)),
+        Color::Green => Some(GridPos::new(0, 9)),
+        Color::Yellow => Some(GridPos::new(9, 9)),
+        Color::Blue => Some(GridPos::new(9, 0)),
+        _ => None,
+    }
+}
+
+// Square a piece waits on in its yard, the middle four of the yard
+pub fn yard_position(color: Color, piece: usize) -> Option<GridPos> {
+    let corner = yard_corner(color)?;
+    Some(GridPos::new(
+        corner.row + 2 + piece as u8 / 2,
+        corner.col + 2 + piece as u8 % 2,
+    ))
+}
+
+// Centre square at the end of the color's home column
+pub fn finish_position(color: Color) -> Option<GridPos> {
+    match color {
+        Color::Red => Some(GridPos::new(7, 6)),
+        Color::Green => Some(GridPos::new(6, 7)),
+        Color::Yellow => Some(GridPos::new(7, 8)),
+        Color::Blue => Some(GridPos::new(8, 7)),
+        _ => None,
+    }
+}
+
+// Where a piece is drawn, if the board has a grid
+pub fn piece_position(state: &LudoGameState, player: usize, piece: usize) -> Option<GridPos> {
+    if state.board != BoardLayout::Classic {
+        return None;
+    }
+    let color = state.players[player].color;
+    let position = state.players[player].pieces[piece].position;
+    match state.players[player].pieces[piece].status {
+        PieceStatus::Home => yard_position(color, piece),
+        PieceStatus::Active => cell_position(state.path(player)[position as usize]),
+        PieceStatus::Win => finish_position(color),
+    }
+}
+
+fn initial(color: Color) -> char {
+    match color {
+        Color::Red => 'R',
+        Color::Green => 'G',
+        Color::Blue => 'B',
+        Color::Yellow => 'Y',
+        Color::Purple => 'P',
+        Color::Orange => 'O',
+    }
+}
+
+// One line per player listing where each piece is, the player to move marked
+fn piece_list(state: &LudoGameState) -> Vec<String> {
+    state
+        .players
+        .iter()
+        .enumerate()
+        .map(|(player_index, player)| {
+            let pieces: Vec<String> = player
+                .pieces
+                .iter()
+                .enumerate()
+                .map(|(i, piece)| match piece.status {
+                    PieceStatus::Home => format!("{}:home", i),
+                    PieceStatus::Win => format!("{}:done", i),
+                    PieceStatus::Active => format!("{}:{}", i, piece.position),
+                })
+                .collect();
+            let marker = if player_index == state.current_player {
+                '>'
+            } else {
+                ' '
+            };
+            format!(
+                "{} {:<8}{}",
+                marker,
+                format!("{:?}", player.color),
+                pieces.join(" ")
+            )
+        })
+        .collect()
+}
+
+// Draws a classic board with one three-character square per grid cell, pieces
+// showing as their color's initial and index, followed by every player's
+// pieces. Other boards only get the piece list.
+pub fn render_ascii(state: &LudoGameState) -> String {
+    let mut out = String::new();
+    if state.board == BoardLayout::Classic {
+        let size = GRID_SIZE as usize;
+        let mut grid = vec![vec![String::from("   "); size]; size];
+        for cell in BoardGeometry::classic().cells {
+            grid[cell.row as usize][cell.col as usize] = String::from(" . ");
+        }
+        for row in grid.iter_mut().take(9).skip(6) {
+            for square in row.iter_mut().take(9).skip(6) {
+                *square = String::from(" # ");
+            }
+        }
+
+        for (player_index, player) in state.players.iter().enumerate() {
+            for i in 0..player.pieces.len() {
+                let Some(pos) = piece_position(state, player_index, i) else {
+                    continue;
+                };
+                // Several pieces on one square show the first initial and a plus
+                let square = &mut grid[pos.row as usize][pos.col as usize];
+                *square = match square.trim() {
+                    "." | "#" | "" => format!(" {}{}", initial(player.color), i),
+                    other => format!(" {}+", &other[..1]),
+                };
+            }
+        }
+
+        for row in &grid {
+            out.push_str(row.concat().trim_end());
+            out.push('\n');
+        }
+    }
+    for line in piece_list(state) {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+fn fill(color: Color) -> &'static str {
+    match color {
+        Color::Red => "#d32f2f",
+        Color::Green => "#388e3c",
+        Color::Blue => "#1976d2",
+        Color::Yellow => "#fbc02d",
+        Color::Purple => "#7b1fa2",
+        Color::Orange => "#f57c00",
+    }
+}
+
+// Pixels per grid square
+const SQUARE: u32 = 40;
+
+fn rect(pos: GridPos, squares: u32, fill: &str, opacity: &str) -> String {
+    format!(
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" fill-opacity=\"{}\" stroke=\"#444\"/>",
+        pos.col as u32 * SQUARE,
+        pos.row as u32 * SQUARE,
+        squares * SQUARE,
+        squares * SQUARE,
+        fill,
+        opacity
+    )
+}
+
+// Draws the board as a standalone SVG document: yards, the track with each
+// color's start square and home column tinted, the centre and every piece.
+// Pieces sharing a square are fanned out. Other boards only get the piece list.
+pub fn render_svg(state: &LudoGameState) -> String {
+    let lines = piece_list(state);
+    let board_px = match state.board {
+        BoardLayout::Classic => GRID_SIZE as u32 * SQUARE,
+        BoardLayout::SixPlayer => 0,
+    };
+    let width = board_px.max(400);
+    let height = board_px + 20 * lines.len() as u32 + 10;
+    let mut svg = vec![format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+        width, height, width, height
+    )];
+    svg.push(format!(
+        "<rect width=\"{}\" height=\"{}\" fill=\"#fff\"/>",
+        width, height
+    ));
+
+    if state.board == BoardLayout::Classic {
+        let geometry = BoardGeometry::classic();
+        for cell in &geometry.cells {
+            svg.push(rect(*cell, 1, "#fff", "1"));
+        }
+        for &color in BoardLayout::Classic.colors() {
+            svg.push(rect(yard_corner(color).unwrap(), 6, fill(color), "0.35"));
+            // The start square and the home column, which leads to the win cell
+            let path = BoardLayout::Classic.path(&color);
+            let home_column = &path[path.len() - 6..path.len() - 1];
+            for &cell in home_column.iter().chain(&path[..1]) {
+                svg.push(rect(cell_position(cell).unwrap(), 1, fill(color), "0.6"));
+            }
+            // Centre triangle from the color's side of the centre to the middle
+            let (cx, cy) = centre_px(CENTRE);
+            let (fx, fy) = centre_px(finish_position(color).unwrap());
+            let (mx, my) = (fx + (fx - cx) / 2, fy + (fy - cy) / 2);
+            let (dx, dy) = (cy - fy, fx - cx); // Along the side, one square long
+            svg.push(format!(
+                "<polygon points=\"{},{} {},{} {},{}\" fill=\"{}\" stroke=\"#444\"/>",
+                cx,
+                cy,
+                mx + dx * 3 / 2,
+                my + dy * 3 / 2,
+                mx - dx * 3 / 2,
+                my - dy * 3 / 2,
+                fill(color)
+            ));
+        }
+
+        let mut drawn: Vec<GridPos> = vec![];
+        for (player_index, player) in state.players.iter().enumerate() {
+            for i in 0..player.pieces.len() {
+                let Some(pos) = piece_position(state, player_index, i) else {
+                    continue;
+                };
+                let stacked = drawn.iter().filter(|&&p| p == pos).count() as i32;
+                drawn.push(pos);
+                let (x, y) = centre_px(pos);
+                let (x, y) = (x + stacked * 5, y - stacked * 5);
+                svg.push(format!(
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\" stroke=\"#000\" stroke-width=\"2\"/>",
+                    x,
+                    y,
+                    SQUARE * 3 / 10,
+                    fill(player.color)
+                ));
+                svg.push(format!(
+                    "<text x=\"{}\" y=\"{}\" font-family=\"sans-serif\" font-size=\"12\" text-anchor=\"middle\" fill=\"#fff\">{}</text>",
+                    x,
+                    y + 4,
+                    i
+                ));
+            }
+        }
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        svg.push(format!(
+            "<text x=\"10\" y=\"{}\" font-family=\"monospace\" font-size=\"14\" xml:space=\"preserve\">{}</text>",
+            board_px + 20 * (i as u32 + 1),
+            line.replace('>', "&gt;")
+        ));
+    }
+    svg.push("</svg>".to_string());
+    svg.join("\n")
+}
+
+// Middle of a square in pixels
+fn centre_px(pos: GridPos) -> (i32, i32) {
+    let half = SQUARE as i32 / 2;
+    (
+        pos.col as i32 * SQUARE as i32 + half,
+        pos.row as i32 * SQUARE as i32 + half,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Piece, Player};
+
+    fn state() -> LudoGameState {
+        let players = BoardLayout::Classic
+            .colors()
+            .iter()
+            .map(|&color| {
+                Player::new(
+                    format!("{:?}", color),
+                    color,
+                    [0; 4].map(|_| Piece::new(-1, PieceStatus::Home)),
+                )
+            })
+            .collect();
+        LudoGameState::new(BoardLayout::Classic, players)
+    }
+
+    #[test]
+    fn board_shows_pieces_on_their_squares() {
+        let geometry = BoardGeometry::classic();
+        // Every cell has its own square, off the yards and the centre
+        let mut squares = geometry.cells.clone();
+        squares.sort();
+        squares.dedup();
+        assert_eq!(squares.len(), 72);
+        assert!(!squares.contains(&geometry.win));
+        assert_eq!(cell_position(WIN), Some(geometry.win));
+        assert_eq!(cell_position(72), None);
+
+        let mut state = state();
+        state.players[0].pieces[1] = Piece::new(0, PieceStatus::Active);
+        state.players[2].pieces[0] = Piece::new(56, PieceStatus::Win);
+        let board = render_ascii(&state);
+        let rows: Vec<&str> = board.lines().collect();
+        // Red enters on cell 19, the second square of row 6
+        assert_eq!(&rows[6][3..6], " R1");
+        assert!(rows[2].contains("R0"));
+        // Blue's finished piece sits at the bottom of the centre
+        assert_eq!(&rows[8][21..24], " B0");
+        assert!(board.contains("> Red     0:home 1:0 2:home 3:home"));
+        assert!(board.contains("  Blue    0:done"));
+
+        let svg = render_svg(&state);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>"));
+        // Sixteen pieces, and Red's entered piece on its start square
+        assert_eq!(svg.matches("<circle").count(), 16);
+        assert!(svg.contains("<circle cx=\"60\" cy=\"260\""));
+        assert!(svg.contains("&gt; Red"));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{LudoGameState, Play, Resign};
+use alloc::vec::Vec;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+
+// Ed25519 key a player signs their moves with
+pub type PublicKey = [u8; 32];
+
+// Keep move and resignation signatures from being valid for any other message
+const PLAY_DOMAIN: &[u8] = b"ludo-core/play/v2";
+const RESIGN_DOMAIN: &[u8] = b"ludo-core/resign/v1";
+
+// Returns true if `signature` is made by `key` over `message`
+fn verifies(key: &PublicKey, message: &[u8], signature: &Option<Vec<u8>>) -> bool {
+    let Some(signature) = signature else {
+        return false;
+    };
+    match (
+        VerifyingKey::from_bytes(key),
+        Signature::from_slice(signature),
+    ) {
+        (Ok(key), Ok(signature)) => key.verify_strict(message, &signature).is_ok(),
+        _ => false,
+    }
+}
+
+// Returns true if `player` has no key, or `signature` is theirs over `message`
+fn is_signed_by(
+    state: &LudoGameState,
+    player: usize,
+    message: &[u8],
+    signature: &Option<Vec<u8>>,
+) -> bool {
+    match &state.players[player].public_key {
+        Some(key) => verifies(key, message, signature),
+        None => true,
+    }
+}
+
+impl Play {
+    // Bytes the player to move signs: the game, the number of this move, the
+    // dice, the chosen piece and the root of the state the move is played on
+    pub fn signing_message(&self, state: &LudoGameState) -> Vec<u8> {
+        let mut message = PLAY_DOMAIN.to_vec();
+        message.extend_from_slice(&state.game_id.0);
+        message.extend_from_slice(&(state.move_number + 1).to_le_bytes());
+        message.push(self.dice_roll);
+        message.push(self.piece_index);
+        message.extend_from_slice(state.digest().as_bytes());
+        message
+    }
+
+    pub fn signed(mut self, key: &SigningKey, state: &LudoGameState) -> Self {
+        let signature = key.sign(&self.signing_message(state));
+        self.signature = Some(signature.to_bytes().to_vec());
+        self
+    }
+
+    // Returns true if the player to move has no key, or signed this move
+    pub fn is_authorized(&self, state: &LudoGameState) -> bool {
+        is_signed_by(
+            state,
+            state.current_player,
+            &self.signing_message(state),
+            &self.signature,
+        )
+    }
+}
+
+impl Resign {
+    // Bytes a resigning player signs: the game, the number of this move, the
+    // player and the root of the state they leave
+    pub fn signing_message(&self, state: &LudoGameState) -> Vec<u8> {
+        let mut message = RESIGN_DOMAIN.to_vec();
+        message.extend_from_slice(&state.game_id.0);
+        message.extend_from_slice(&(state.move_number + 1).to_le_bytes());
+        message.push(self.player as u8);
+        message.extend_from_slice(state.digest().as_bytes());
+        message
+    }
+
+    pub fn signed(mut self, key: &SigningKey, state: &LudoGameState) -> Self {
+        let signature = key.sign(&self.signing_message(state));
+        self.signature = Some(signature.to_bytes().to_vec());
+        self
+    }
+
+    // Returns true if the resigning player has no key, or signed this
+    // resignation, or the game's arbiter did. The arbiter forfeits players who
+    // let their turn expire, as they cannot be made to sign.
+    pub fn is_authorized(&self, state: &LudoGameState) -> bool {
+        let message = self.signing_message(state);
+        is_signed_by(state, self.player, &message, &self.signature)
+            || state
+                .arbiter
+                .is_some_and(|arbiter| verifies(&arbiter, &message, &self.signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoardLayout, GameId, Piece, PieceStatus, PlayGameParams, Player};
+    use alloc::format;
+
+    #[test]
+    fn signature_binds_move_to_player_and_state() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let players = BoardLayout::Classic
+            .colors()
+            .iter()
+            .map(|c| {
+                Player::new(
+                    format!("{:?}", c),
+                    *c,
+                    [0; 4].map(|_| Piece::new(-1, PieceStatus::Home)),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut state =
+            LudoGameState::new(BoardLayout::Classic, players).with_game_id(GameId([3; 16]));
+        state.players[0].public_key = Some(key.verifying_key().to_bytes());
+
+        let play = Play {
+            current_player: 0,
+            dice_roll: 6,
+            piece_index: 1,
+            signature: None,
+        };
+        assert!(!play.is_authorized(&state));
+
+        let signed = play.clone().signed(&key, &state);
+        assert!(signed.is_authorized(&state));
+
+        // Changing the dice or piece, or replaying the signature in another game fails
+        let mut other_dice = signed.clone();
+        other_dice.dice_roll = 5;
+        assert!(!other_dice.is_authorized(&state));
+        let mut other_piece = signed.clone();
+        other_piece.piece_index = 2;
+        assert!(!other_piece.is_authorized(&state));
+        assert!(!signed.is_authorized(&state.clone().with_game_id(GameId([4; 16]))));
+
+        // Only the player can resign themselves
+        let resign = Resign {
+            player: 0,
+            signature: None,
+        };
+        assert!(!resign.is_authorized(&state));
+        assert!(!resign
+            .clone()
+            .signed(&SigningKey::from_bytes(&[8; 32]), &state)
+            .is_authorized(&state));
+        assert!(resign.clone().signed(&key, &state).is_authorized(&state));
+
+        // ...or the arbiter the game was started with
+        let arbiter = SigningKey::from_bytes(&[9; 32]);
+        let refereed = state
+            .clone()
+            .with_arbiter(arbiter.verifying_key().to_bytes());
+        assert!(resign.signed(&arbiter, &refereed).is_authorized(&refereed));
+
+        let result = PlayGameParams::new(state, signed).process().unwrap();
+        assert_eq!(
+            result.state.players[0].pieces[1].status,
+            PieceStatus::Active
+        );
+    }
+}
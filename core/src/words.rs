@@ -0,0 +1,572 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::Deserialize;
+
+// Reader for the word format the guests commit their journals in, so receipts
+// can be decoded without the zkVM crates (in a browser, for example). Values up
+// to 32 bits take one little-endian word and 64-bit values, usize included,
+// take two with the low word first. Options and enum variants start with a tag
+// word; sequences, maps, strings and byte strings with their length. Strings
+// and bytes are padded to whole words.
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WordsError {
+    UnexpectedEnd,  // The value needs more words than there are
+    TrailingWords,  // Words are left over after the value
+    InvalidValue,   // A tag or value does not fit its type
+    Unsupported,    // The format is not self-describing, so `deserialize_any` cannot work
+    Custom(String), // Raised by a `Deserialize` implementation
+}
+
+impl fmt::Display for WordsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WordsError::UnexpectedEnd => write!(f, "Unexpected end of words"),
+            WordsError::TrailingWords => write!(f, "Words left after the value"),
+            WordsError::InvalidValue => write!(f, "Invalid value for its type"),
+            WordsError::Unsupported => write!(f, "Unsupported by the word format"),
+            WordsError::Custom(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl de::StdError for WordsError {}
+
+impl de::Error for WordsError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        WordsError::Custom(message.to_string())
+    }
+}
+
+// Decodes one value that spans all of `bytes`
+pub fn from_slice<'de, T: Deserialize<'de>>(bytes: &'de [u8]) -> Result<T, WordsError> {
+    if !bytes.len().is_multiple_of(4) {
+        return Err(WordsError::InvalidValue);
+    }
+    let mut deserializer = Deserializer { bytes };
+    let value = T::deserialize(&mut deserializer)?;
+    match deserializer.bytes.is_empty() {
+        true => Ok(value),
+        false => Err(WordsError::TrailingWords),
+    }
+}
+
+struct Deserializer<'de> {
+    bytes: &'de [u8],
+}
+
+impl<'de> Deserializer<'de> {
+    fn take(&mut self, len: usize) -> Result<&'de [u8], WordsError> {
+        if self.bytes.len() < len {
+            return Err(WordsError::UnexpectedEnd);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn word(&mut self) -> Result<u32, WordsError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn double_word(&mut self) -> Result<u64, WordsError> {
+        let low = self.word()? as u64;
+        Ok(low | (self.word()? as u64) << 32)
+    }
+
+    fn small<T: TryFrom<u32>>(&mut self) -> Result<T, WordsError> {
+        T::try_from(self.word()?).map_err(|_| WordsError::InvalidValue)
+    }
+
+    fn len(&mut self) -> Result<usize, WordsError> {
+        Ok(self.word()? as usize)
+    }
+
+    // A length word, then the bytes padded to a whole number of words
+    fn padded_bytes(&mut self) -> Result<&'de [u8], WordsError> {
+        let len = self.len()?;
+        let padded = len
+            .checked_next_multiple_of(4)
+            .ok_or(WordsError::InvalidValue)?;
+        Ok(&self.take(padded)?[..len])
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = WordsError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, WordsError> {
+        Err(WordsError::Unsupported)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        match self.word()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            _ => Err(WordsError::InvalidValue),
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        let value = i8::try_from(self.word()? as i32).map_err(|_| WordsError::InvalidValue)?;
+        visitor.visit_i8(value)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        let value = i16::try_from(self.word()? as i32).map_err(|_| WordsError::InvalidValue)?;
+        visitor.visit_i16(value)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        visitor.visit_i32(self.word()? as i32)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        visitor.visit_i64(self.double_word()? as i64)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        visitor.visit_u8(self.small()?)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        visitor.visit_u16(self.small()?)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        visitor.visit_u32(self.word()?)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        visitor.visit_u64(self.double_word()?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        visitor.visit_f32(f32::from_bits(self.word()?))
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        visitor.visit_f64(f64::from_bits(self.double_word()?))
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        let value = char::from_u32(self.word()?).ok_or(WordsError::InvalidValue)?;
+        visitor.visit_char(value)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        let value =
+            core::str::from_utf8(self.padded_bytes()?).map_err(|_| WordsError::InvalidValue)?;
+        visitor.visit_borrowed_str(value)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        visitor.visit_borrowed_bytes(self.padded_bytes()?)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        match self.word()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            _ => Err(WordsError::InvalidValue),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, WordsError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, WordsError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        let len = self.len()?;
+        visitor.visit_seq(Elements { de: self, len })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, WordsError> {
+        visitor.visit_seq(Elements { de: self, len })
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, WordsError> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        let len = self.len()?;
+        visitor.visit_map(Elements { de: self, len })
+    }
+
+    // Fields follow one another without names or a count
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, WordsError> {
+        self.deserialize_tuple(fields.len(), visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, WordsError> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, WordsError> {
+        self.deserialize_u32(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, WordsError> {
+        Err(WordsError::Unsupported)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+// The next `len` elements of a sequence, tuple or map
+struct Elements<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    len: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for Elements<'_, 'de> {
+    type Error = WordsError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, WordsError> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+impl<'de> de::MapAccess<'de> for Elements<'_, 'de> {
+    type Error = WordsError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, WordsError> {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, WordsError> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
+
+// Variants are read by index, then their fields like a tuple
+impl<'de> de::EnumAccess<'de> for &mut Deserializer<'de> {
+    type Error = WordsError;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), WordsError> {
+        let index: u32 = self.word()?;
+        let variant = seed.deserialize(index.into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for &mut Deserializer<'de> {
+    type Error = WordsError;
+
+    fn unit_variant(self) -> Result<(), WordsError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, WordsError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, WordsError> {
+        de::Deserializer::deserialize_tuple(self, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, WordsError> {
+        de::Deserializer::deserialize_tuple(self, fields.len(), visitor)
+    }
+}
+
+// Journals of every layout the guests commit, next to their words written out
+// by hand from the format above. The journal tests check the guests' own
+// serializer still produces exactly these words.
+#[cfg(test)]
+pub(crate) mod golden {
+    use crate::journal::{
+        ActionV2, GameId, HiddenPlayCommit, PiecePosition, PlayGameCommit, PlayGameCommitV1,
+        PlayGameCommitV2,
+    };
+    use crate::{
+        Action, BoardLayout, Digest, DisclosureCommit, EndCondition, InitializeGameStateCommit,
+        PieceRef, Placement, Play, Statement, WinnersCommit,
+    };
+    use alloc::{vec, vec::Vec};
+
+    const GAME_ID: GameId = GameId([7; 16]);
+    const OLD: Digest = Digest::new([1; 8]);
+    const NEW: Digest = Digest::new([2; 8]);
+
+    // The words of a value, then the words after it
+    fn words(parts: &[&[u32]]) -> Vec<u32> {
+        parts.concat()
+    }
+
+    pub(crate) fn init() -> (InitializeGameStateCommit, Vec<u32>) {
+        let commit = InitializeGameStateCommit {
+            game_id: GAME_ID,
+            salted: true,
+            board: BoardLayout::SixPlayer,
+            current_player: 2,
+            dice_roll: 0,
+            winners: vec![3],
+            sixes: 1,
+            state_hash: NEW,
+        };
+        // Game id, salted, board, player over two words, dice, one winner,
+        // sixes, hash
+        let words = words(&[&[7; 16], &[1, 1, 2, 0, 0, 1, 3, 0, 1], &[2; 8]]);
+        (commit, words)
+    }
+
+    pub(crate) fn play_v1() -> (PlayGameCommitV1, Vec<u32>) {
+        let commit = PlayGameCommitV1 {
+            old_state: OLD,
+            new_state: NEW,
+            current_player: 1,
+            dice_roll: 6,
+            piece_index: 2,
+        };
+        (commit, words(&[&[1; 8], &[2; 8], &[1, 0, 6, 2]]))
+    }
+
+    pub(crate) fn play_v2() -> (PlayGameCommitV2, Vec<u32>) {
+        let commit = PlayGameCommitV2 {
+            version: 2,
+            game_id: GAME_ID,
+            move_number: 5,
+            old_state: OLD,
+            new_state: NEW,
+            action: ActionV2::Resign { player: 1 },
+            next_player: 2,
+            moved_piece: None,
+            captured: None,
+            winners: vec![],
+        };
+        // Resign is variant 1 with its player; then the next player, two
+        // empty options and no winners
+        let words = words(&[
+            &[2],
+            &[7; 16],
+            &[5],
+            &[1; 8],
+            &[2; 8],
+            &[1, 1, 0, 2, 0, 0, 0, 0],
+        ]);
+        (commit, words)
+    }
+
+    pub(crate) fn play_v3() -> (HiddenPlayCommit, Vec<u32>) {
+        let commit = HiddenPlayCommit {
+            version: 3,
+            game_id: GAME_ID,
+            move_number: 9,
+            old_state: OLD,
+            new_state: NEW,
+        };
+        (commit, words(&[&[3], &[7; 16], &[9], &[1; 8], &[2; 8]]))
+    }
+
+    pub(crate) fn play_v4() -> (PlayGameCommit, Vec<u32>) {
+        let commit = PlayGameCommit {
+            version: 4,
+            game_id: GAME_ID,
+            move_number: 1,
+            old_state: OLD,
+            new_state: NEW,
+            action: Action::Play(Play {
+                current_player: 0,
+                dice_roll: 6,
+                piece_index: 2,
+                signature: Some(vec![9, 8, 7]),
+            }),
+            next_player: 0,
+            moved_piece: Some(PiecePosition {
+                piece: PieceRef {
+                    player: 0,
+                    piece: 2,
+                },
+                position: -1,
+            }),
+            captured: Some(PieceRef {
+                player: 1,
+                piece: 3,
+            }),
+            winners: vec![1],
+        };
+        // Play is variant 0; the signature is a list of one word per byte.
+        // Positions are signed, so -1 is all ones.
+        let words = words(&[
+            &[4],
+            &[7; 16],
+            &[1],
+            &[1; 8],
+            &[2; 8],
+            &[0, 0, 0, 6, 2, 1, 3, 9, 8, 7],
+            &[0, 0],
+            &[1, 0, 0, 2, u32::MAX],
+            &[1, 1, 0, 3],
+            &[1, 1, 0],
+        ]);
+        (commit, words)
+    }
+
+    pub(crate) fn winners() -> (WinnersCommit, Vec<u32>) {
+        let commit = WinnersCommit {
+            game_id: GAME_ID,
+            move_number: 40,
+            state_hash: NEW,
+            end_condition: EndCondition::TopN(2),
+            winners: vec![1],
+            placements: vec![Placement {
+                player: 1,
+                rank: 1,
+                pieces_finished: 4,
+                progress: 224,
+                eliminated: false,
+            }],
+        };
+        // TopN is variant 1 with its count, then one winner and one placement
+        let words = words(&[
+            &[7; 16],
+            &[40],
+            &[2; 8],
+            &[1, 2],
+            &[1, 1, 0],
+            &[1, 1, 0, 1, 0, 4, 224, 0],
+        ]);
+        (commit, words)
+    }
+
+    pub(crate) fn disclosure() -> (DisclosureCommit, Vec<u32>) {
+        let commit = DisclosureCommit {
+            state_root: NEW,
+            statement: Statement::PieceAtCell {
+                player: 1,
+                piece: 3,
+                cell: 70,
+            },
+        };
+        (commit, words(&[&[2; 8], &[1, 1, 0, 3, 70]]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn bytes(words: &[u32]) -> Vec<u8> {
+        words.iter().flat_map(|w| w.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn golden_journals_decode() {
+        let (init, words) = golden::init();
+        assert_eq!(from_slice(&bytes(&words)), Ok(init));
+        let (v1, words) = golden::play_v1();
+        assert_eq!(from_slice(&bytes(&words)), Ok(v1));
+        let (v2, words) = golden::play_v2();
+        assert_eq!(from_slice(&bytes(&words)), Ok(v2));
+        let (v3, words) = golden::play_v3();
+        assert_eq!(from_slice(&bytes(&words)), Ok(v3));
+        let (v4, words) = golden::play_v4();
+        assert_eq!(from_slice(&bytes(&words)), Ok(v4.clone()));
+        let (winners, words) = golden::winners();
+        assert_eq!(from_slice(&bytes(&words)), Ok(winners));
+        let (disclosure, words) = golden::disclosure();
+        assert_eq!(from_slice(&bytes(&words)), Ok(disclosure));
+
+        // Every word must be used, and no more than there are
+        let (_, mut words) = golden::play_v4();
+        words.push(0);
+        let decoded = from_slice::<crate::PlayGameCommit>(&bytes(&words));
+        assert_eq!(decoded, Err(WordsError::TrailingWords));
+        words.truncate(words.len() - 2);
+        let decoded = from_slice::<crate::PlayGameCommit>(&bytes(&words));
+        assert_eq!(decoded, Err(WordsError::UnexpectedEnd));
+        assert_eq!(
+            from_slice::<bool>(&bytes(&[2])),
+            Err(WordsError::InvalidValue)
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use crate::{Game, InitMessage, PlayMessage};
+use anyhow::{anyhow, ensure};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use ludo_core::{
+    Action, Digest, GameId, LudoGameState, Play, PlayGameCommit, PlayGameParams, PublicKey,
+};
+use risc0_zkvm::Result;
+use std::collections::BTreeMap;
+
+// Keeps channel signatures from being valid for a move or any other message
+const CHANNEL_DOMAIN: &[u8] = b"ludo-host/channel/v1";
+
+// Bytes every player signs to agree on the state reached by a commit
+pub fn channel_message(game_id: GameId, move_number: u32, state_root: Digest) -> Vec<u8> {
+    let mut message = CHANNEL_DOMAIN.to_vec();
+    message.extend_from_slice(&game_id.0);
+    message.extend_from_slice(&move_number.to_le_bytes());
+    message.extend_from_slice(state_root.as_bytes());
+    message
+}
+
+// A play commit computed off-chain, with the signatures of the players that agreed to it
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedCommit {
+    pub commit: PlayGameCommit,
+    pub signatures: BTreeMap<usize, Vec<u8>>, // Ed25519 signatures by player index
+}
+
+impl SignedCommit {
+    fn message(&self) -> Vec<u8> {
+        channel_message(
+            self.commit.game_id,
+            self.commit.move_number,
+            self.commit.new_state,
+        )
+    }
+
+    fn sign(&mut self, player: usize, key: &SigningKey) {
+        let signature = key.sign(&self.message());
+        self.signatures
+            .insert(player, signature.to_bytes().to_vec());
+    }
+
+    // Returns true if every key in `public_keys` signed the commit
+    pub fn is_cosigned(&self, public_keys: &[PublicKey]) -> bool {
+        let message = self.message();
+        public_keys.iter().enumerate().all(|(player, key)| {
+            let Some(signature) = self.signatures.get(&player) else {
+                return false;
+            };
+            match (
+                VerifyingKey::from_bytes(key),
+                Signature::from_slice(signature),
+            ) {
+                (Ok(key), Ok(signature)) => key.verify_strict(&message, &signature).is_ok(),
+                _ => false,
+            }
+        })
+    }
+}
+
+// One player's end of a channel. Every peer replays each action on its own
+// copy of the state, so nobody has to prove anything while all agree.
+// Channels do not roll dice: the player to move names the roll, so they are
+// only suited to games where the players roll at the same physical table.
+#[derive(Debug)]
+pub struct Peer {
+    pub player: usize,
+    key: SigningKey,
+    pub state: LudoGameState,         // State after the latest agreed commit
+    pub latest: Option<SignedCommit>, // Latest commit signed by every player
+    pending: Option<(SignedCommit, LudoGameState)>,
+}
+
+impl Peer {
+    pub fn new(player: usize, key: SigningKey, state: LudoGameState) -> Self {
+        Self {
+            player,
+            key,
+            state,
+            latest: None,
+            pending: None,
+        }
+    }
+
+    fn public_keys(&self) -> Vec<PublicKey> {
+        self.state
+            .players
+            .iter()
+            .map(|p| p.public_key.expect("Every channel player needs a key"))
+            .collect()
+    }
+
+    // Applies an action locally and builds the commit it produces
+    fn apply(&self, action: Action) -> Result<(SignedCommit, LudoGameState)> {
+        let params = PlayGameParams::new(self.state.clone(), action);
+        let result = params.process()?;
+        let commit =
+            PlayGameCommit::new(&params, &result, self.state.digest(), result.state.digest());
+        let signed = SignedCommit {
+            commit,
+            signatures: BTreeMap::new(),
+        };
+        Ok((signed, result.state))
+    }
+
+    // Plays a move on our own turn and signs the resulting commit
+    pub fn propose(&mut self, dice_roll: u8, piece_index: u8) -> Result<SignedCommit> {
+        ensure!(
+            self.state.current_player == self.player,
+            "Not this player's turn"
+        );
+        let play = Play {
+            current_player: self.player,
+            dice_roll,
+            piece_index,
+            signature: None,
+        }
+        .signed(&self.key, &self.state);
+        let (mut signed, state) = self.apply(Action::Play(play))?;
+        signed.sign(self.player, &self.key);
+        self.pending = Some((signed.clone(), state));
+        Ok(signed)
+    }
+
+    // Replays another player's proposal and adds our signature if we reach the same commit
+    pub fn countersign(&mut self, proposal: &SignedCommit) -> Result<SignedCommit> {
+        let (replayed, state) = self.apply(proposal.commit.action.clone())?;
+        ensure!(
+            replayed.commit == proposal.commit,
+            "Proposal does not follow from the agreed state"
+        );
+        let mut signed = proposal.clone();
+        signed.sign(self.player, &self.key);
+        self.pending = Some((signed.clone(), state));
+        Ok(signed)
+    }
+
+    // Adopts a commit once every player has signed it
+    pub fn accept(&mut self, signed: &SignedCommit) -> Result<()> {
+        ensure!(
+            signed.is_cosigned(&self.public_keys()),
+            "Commit is not signed by every player"
+        );
+        let (replayed, state) = match self.pending.take() {
+            Some((pending, state)) if pending.commit == signed.commit => (pending, state),
+            _ => self.apply(signed.commit.action.clone())?,
+        };
+        ensure!(
+            replayed.commit == signed.commit,
+            "Commit does not follow from the agreed state"
+        );
+        self.state = state;
+        self.latest = Some(signed.clone());
+        Ok(())
+    }
+
+    // Proves our next move on top of the latest agreed state, for when the
+    // other players stop co-signing
+    pub fn prove_move(&self, dice_roll: u8, piece_index: u8) -> Result<PlayMessage> {
+        let play = Play {
+            current_player: self.player,
+            dice_roll,
+            piece_index,
+            signature: None,
+        }
+        .signed(&self.key, &self.state);
+        Game::new(self.state.clone()).play(&play)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Resolution {
+    Open,
+    Forfeit { player: usize }, // The player failed to answer a challenge in time
+}
+
+// Stands in for the settlement contract. It only ever sees co-signed states
+// and proofs, and otherwise stays idle while the peers agree.
+#[derive(Debug)]
+pub struct Adjudicator {
+    game_id: GameId,
+    public_keys: Vec<PublicKey>,
+    response_window: u64, // Seconds the challenged player has to answer
+    move_number: u32,
+    state_root: Digest,
+    pending: Option<(usize, u64)>, // Player that must move next, and their deadline
+    resolution: Resolution,
+}
+
+impl Adjudicator {
+    pub fn new(
+        init: &InitMessage,
+        public_keys: Vec<PublicKey>,
+        response_window: u64,
+    ) -> Result<Self> {
+        let commit = init.verify_and_get_commit()?;
+        ensure!(!commit.salted, "Channels need public state roots");
+        Ok(Self {
+            game_id: commit.game_id,
+            public_keys,
+            response_window,
+            move_number: 0,
+            state_root: commit.state_hash,
+            pending: None,
+            resolution: Resolution::Open,
+        })
+    }
+
+    pub fn move_number(&self) -> u32 {
+        self.move_number
+    }
+
+    pub fn state_root(&self) -> Digest {
+        self.state_root
+    }
+
+    pub fn resolution(&self) -> &Resolution {
+        &self.resolution
+    }
+
+    // Opens a dispute: the challenger submits the latest co-signed commit and,
+    // if it is their turn, a proof of their next move. The next player then
+    // has to answer with a proof of their own move before the deadline.
+    pub fn challenge(
+        &mut self,
+        latest: Option<&SignedCommit>,
+        next_move: Option<&PlayMessage>,
+        now: u64,
+    ) -> Result<()> {
+        ensure!(
+            self.resolution == Resolution::Open,
+            "Channel is already settled"
+        );
+        if let Some(latest) = latest {
+            ensure!(
+                latest.commit.game_id == self.game_id,
+                "Commit belongs to another game"
+            );
+            ensure!(
+                latest.commit.move_number >= self.move_number,
+                "A later state has already been submitted"
+            );
+            ensure!(
+                latest.is_cosigned(&self.public_keys),
+                "Commit is not signed by every player"
+            );
+            // Submitting the current state again keeps the running deadline
+            if latest.commit.move_number == self.move_number {
+                ensure!(
+                    latest.commit.new_state == self.state_root,
+                    "Commit conflicts with the submitted state"
+                );
+            } else {
+                self.move_number = latest.commit.move_number;
+                self.state_root = latest.commit.new_state;
+                self.pending = Some((latest.commit.next_player, now + self.response_window));
+            }
+        }
+        match next_move {
+            Some(next_move) => self.respond(next_move, now),
+            None => Ok(()),
+        }
+    }
+
+    // Answers a challenge with a proof of the move that continues the submitted state
+    pub fn respond(&mut self, next_move: &PlayMessage, now: u64) -> Result<()> {
+        ensure!(
+            self.resolution == Resolution::Open,
+            "Channel is already settled"
+        );
+        let commit = next_move.verify_and_get_commit()?;
+        ensure!(
+            commit.game_id == self.game_id,
+            "Receipt belongs to another game"
+        );
+        ensure!(
+            commit.move_number == self.move_number + 1,
+            "Expected move {}, receipt is for move {}",
+            self.move_number + 1,
+            commit.move_number
+        );
+        ensure!(
+            commit.old_state == self.state_root,
+            "Receipt does not continue from the submitted state"
+        );
+        if let Some((player, deadline)) = self.pending {
+            ensure!(now <= deadline, "Player {} answered too late", player);
+            // Only the challenged player's own move or resignation answers
+            let actor = match &commit.action {
+                Action::Play(play) => play.current_player,
+                Action::Resign(resign) => resign.player,
+            };
+            ensure!(actor == player, "Only player {} can answer", player);
+        }
+
+        self.move_number = commit.move_number;
+        self.state_root = commit.new_state;
+        self.pending = Some((commit.next_player, now + self.response_window));
+        Ok(())
+    }
+
+    // Forfeits the challenged player once their deadline has passed
+    pub fn claim_timeout(&mut self, now: u64) -> Result<usize> {
+        ensure!(
+            self.resolution == Resolution::Open,
+            "Channel is already settled"
+        );
+        let (player, deadline) = self.pending.ok_or_else(|| anyhow!("No open challenge"))?;
+        ensure!(now > deadline, "Player {} still has time to answer", player);
+        self.resolution = Resolution::Forfeit { player };
+        Ok(player)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players;
+    use ludo_core::BoardLayout;
+
+    fn peers() -> Vec<Peer> {
+        let keys: Vec<SigningKey> = (0..4)
+            .map(|i| SigningKey::from_bytes(&[i + 1; 32]))
+            .collect();
+        let players = players::get_players(BoardLayout::Classic)
+            .into_iter()
+            .zip(&keys)
+            .map(|(p, key)| p.with_public_key(key.verifying_key().to_bytes()))
+            .collect();
+        let state = LudoGameState::new(BoardLayout::Classic, players).with_game_id(GameId([5; 16]));
+        keys.into_iter()
+            .enumerate()
+            .map(|(i, key)| Peer::new(i, key, state.clone()))
+            .collect()
+    }
+
+    // Passes a proposal around the table and hands the co-signed commit back to everyone
+    fn agree(peers: &mut [Peer], proposer: usize, dice_roll: u8, piece_index: u8) -> SignedCommit {
+        let mut signed = peers[proposer].propose(dice_roll, piece_index).unwrap();
+        for i in (0..peers.len()).filter(|&i| i != proposer) {
+            signed = peers[i].countersign(&signed).unwrap();
+        }
+        for peer in peers.iter_mut() {
+            peer.accept(&signed).unwrap();
+        }
+        signed
+    }
+
+    #[test]
+    fn peers_agree_on_moves_without_proofs() {
+        let mut peers = peers();
+        agree(&mut peers, 0, 6, 0);
+        let signed = agree(&mut peers, 0, 3, 0);
+        assert_eq!(signed.commit.move_number, 2);
+        assert!(peers.iter().all(|p| p.state == peers[0].state));
+        assert_eq!(peers[2].state.players[0].pieces[0].position, 3);
+
+        // A proposal that does not follow from the agreed state is refused
+        let mut forged = peers[1].propose(5, 0).unwrap();
+        forged.commit.next_player = 3;
+        assert!(peers[2].countersign(&forged).is_err());
+
+        // Only the player to move can propose
+        assert!(peers[3].propose(6, 0).is_err());
+    }
+
+    #[test]
+    fn challenge_is_answered_with_a_proof_or_forfeited() {
+        let mut peers = peers();
+        let mut game = Game::new(peers[0].state.clone());
+        let init = game.init().expect("Failed to init game");
+        let mut adjudicator = Adjudicator::new(&init, peers[0].public_keys(), 60).unwrap();
+
+        let signed = agree(&mut peers, 0, 4, 0);
+        assert_eq!(signed.commit.next_player, 1);
+
+        // Player 1 stops co-signing, so player 0 takes the last agreed state on-chain
+        adjudicator.challenge(Some(&signed), None, 1_000).unwrap();
+        assert!(adjudicator.claim_timeout(1_030).is_err());
+
+        // Submitting the same commit again does not restart the clock
+        adjudicator.challenge(Some(&signed), None, 1_050).unwrap();
+        let answer = peers[1].prove_move(3, 0).expect("Failed to prove move");
+        assert!(adjudicator.respond(&answer, 1_061).is_err());
+
+        // Player 1 answers with a proven move, which puts player 2 on the clock
+        adjudicator.respond(&answer, 1_060).unwrap();
+        assert_eq!(adjudicator.move_number(), 2);
+        assert!(adjudicator.respond(&answer, 1_070).is_err());
+
+        assert!(adjudicator.claim_timeout(1_120).is_err());
+        assert_eq!(adjudicator.claim_timeout(1_121).unwrap(), 2);
+        assert_eq!(adjudicator.resolution(), &Resolution::Forfeit { player: 2 });
+    }
+}
@@ -0,0 +1,325 @@
+use anyhow::{anyhow, ensure, Context, Result};
+use clap::{Parser, Subcommand};
+use ed25519_dalek::SigningKey;
+use host::replay::prove_record;
+use host::transcript::TranscriptVerifier;
+use host::{players, terminal, DisclosureMessage, Game, InitMessage, PlayMessage, WinnersMessage};
+use ludo_core::{
+    render_ascii, BoardLayout, Digest, EndCondition, GameId, GameRecord, LudoGameState, Notation,
+    Play,
+};
+use methods::{DISCLOSE_ID, INIT_ID, PLAY_ID, WINNERS_ID};
+use rand::Rng;
+use risc0_zkvm::Receipt;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Proves and checks games from the command line. A game lives in a save file,
+/// JSON for `.json` paths and binary otherwise, with its receipts written next
+/// to it: `<stem>.init.receipt`, one `<stem>.<move>.receipt` per action and
+/// `<stem>.winners.receipt` at the end.
+#[derive(Parser)]
+#[command(name = "host")]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Starts a game: writes the save file and proves the init receipt
+    Init {
+        #[arg(long)]
+        state: PathBuf,
+        /// classic or six-player
+        #[arg(long, default_value = "classic")]
+        board: BoardLayout,
+        /// first, last, or top:N
+        #[arg(long, default_value = "last")]
+        end_condition: EndCondition,
+    },
+    /// Proves one move and updates the save file, then the winners once the game ends
+    Play {
+        #[arg(long)]
+        state: PathBuf,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=6))]
+        dice: u8,
+        #[arg(long, default_value_t = 0)]
+        piece: u8,
+        /// Hex ed25519 secret key, for players that registered a public key
+        #[arg(long)]
+        key: Option<String>,
+    },
+    /// Verifies a receipt against every guest and prints its journal
+    Verify { receipt: PathBuf },
+    /// Checks a game's receipts form one transcript and bundles them with the state
+    Export {
+        #[arg(long)]
+        state: PathBuf,
+        /// Defaults to `<stem>.bundle`
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Proves a recorded game again from the start, writing its save file and every receipt
+    Replay {
+        /// Game record as JSON for `.json` paths, game notation otherwise
+        log: PathBuf,
+        #[arg(long)]
+        state: PathBuf,
+    },
+    /// Prints a saved game
+    Inspect {
+        #[arg(long)]
+        state: PathBuf,
+    },
+    /// Plays a game at this terminal
+    Hotseat {
+        #[arg(long)]
+        no_prove: bool,
+        #[arg(long)]
+        six_player: bool,
+    },
+}
+
+// A game's state and every receipt it produced, in order
+#[derive(Serialize, Deserialize)]
+pub struct Bundle {
+    pub state: LudoGameState,
+    pub receipts: Vec<Receipt>,
+}
+
+fn parse_key(hex: &str) -> Result<SigningKey> {
+    ensure!(
+        hex.len() == 64 && hex.is_ascii(),
+        "Key must be 64 hex characters"
+    );
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).context("Key is not hex")?;
+    }
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+// Games started here get a fresh id, so their receipts cannot be mixed up
+fn new_game_id() -> GameId {
+    GameId(rand::thread_rng().gen())
+}
+
+// Receipt path next to the state file, e.g. `game.3.receipt` for `game.json`
+fn receipt_path(state: &Path, name: &str) -> PathBuf {
+    state.with_extension(format!("{}.receipt", name))
+}
+
+fn load(path: &Path) -> Result<Game> {
+    Game::load(path).with_context(|| format!("Loading {}", path.display()))
+}
+
+// Hidden games commit to the salted hash, so compare against what the receipts
+// committed to rather than the plain digest
+fn ensure_saved_state(game: &Game, state_hash: Digest) -> Result<()> {
+    ensure!(
+        state_hash == game.commitment(),
+        "Saved state does not match the last receipt"
+    );
+    Ok(())
+}
+
+fn read_receipt(path: &Path) -> Result<Receipt> {
+    let bytes = fs::read(path).with_context(|| format!("Reading {}", path.display()))?;
+    Ok(bincode::deserialize(&bytes)?)
+}
+
+fn write_receipt(path: &Path, receipt: &Receipt) -> Result<()> {
+    fs::write(path, bincode::serialize(receipt)?)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+pub fn run(cli: Cli) -> Result<()> {
+    match cli.command {
+        Command::Init {
+            state,
+            board,
+            end_condition,
+        } => {
+            let mut game = Game::new(
+                LudoGameState::new(board, players::get_players(board))
+                    .with_game_id(new_game_id())
+                    .with_end_condition(end_condition),
+            );
+            let init = game.init()?;
+            println!("{:#?}", init.verify_and_get_commit()?);
+            game.save(&state)?;
+            write_receipt(&receipt_path(&state, "init"), init.receipt())
+        }
+        Command::Play {
+            state,
+            dice,
+            piece,
+            key,
+        } => {
+            let mut game = load(&state)?;
+            ensure!(!game.state.is_over(), "Game is already over");
+            let mut play = Play {
+                current_player: game.state.current_player,
+                dice_roll: dice,
+                piece_index: piece,
+                signature: None,
+            };
+            if let Some(key) = key {
+                play = play.signed(&parse_key(&key)?, &game.state);
+            }
+
+            let message = game.play(&play)?;
+            println!("{:#?}", message.verify_and_get_journal()?);
+            let move_number = game.state.move_number.to_string();
+            write_receipt(&receipt_path(&state, &move_number), message.receipt())?;
+            if game.state.is_over() {
+                let winners = game.verify_winners()?;
+                println!("{:#?}", winners.verify_and_get_commit()?);
+                write_receipt(&receipt_path(&state, "winners"), &winners.receipt)?;
+            }
+            game.save(&state)
+        }
+        Command::Verify { receipt } => {
+            let receipt = read_receipt(&receipt)?;
+            // Each guest has its own image id, so only the right one verifies
+            if receipt.verify(INIT_ID).is_ok() {
+                println!(
+                    "Init receipt\n{:#?}",
+                    InitMessage::new(receipt).get_state()?
+                );
+            } else if receipt.verify(PLAY_ID).is_ok() {
+                println!(
+                    "Play receipt\n{:#?}",
+                    PlayMessage::new(receipt).get_journal()?
+                );
+            } else if receipt.verify(WINNERS_ID).is_ok() {
+                let message = WinnersMessage { receipt };
+                println!("Winners receipt\n{:#?}", message.verify_and_get_commit()?);
+            } else if receipt.verify(DISCLOSE_ID).is_ok() {
+                let message = DisclosureMessage { receipt };
+                println!(
+                    "Disclosure receipt\n{:#?}",
+                    message.verify_and_get_commit()?
+                );
+            } else {
+                return Err(anyhow!("Receipt does not verify against any guest"));
+            }
+            Ok(())
+        }
+        Command::Export { state, out } => {
+            let game = load(&state)?;
+            let init = InitMessage::new(read_receipt(&receipt_path(&state, "init"))?);
+            let mut transcript = TranscriptVerifier::new(&init)?;
+            let mut receipts = vec![init.receipt().clone()];
+            for move_number in 1..=game.state.move_number {
+                let path = receipt_path(&state, &move_number.to_string());
+                let play = PlayMessage::new(read_receipt(&path)?);
+                transcript.verify_play(&play)?;
+                receipts.push(play.receipt().clone());
+            }
+            let winners_path = receipt_path(&state, "winners");
+            if winners_path.exists() {
+                let winners = WinnersMessage {
+                    receipt: read_receipt(&winners_path)?,
+                };
+                transcript.verify_winners(&winners)?;
+                receipts.push(winners.receipt);
+            }
+            ensure_saved_state(&game, transcript.state_hash())?;
+
+            let out = out.unwrap_or_else(|| state.with_extension("bundle"));
+            let bundle = Bundle {
+                state: game.state,
+                receipts,
+            };
+            fs::write(&out, bincode::serialize(&bundle)?)?;
+            println!(
+                "Wrote {} with {} receipts",
+                out.display(),
+                bundle.receipts.len()
+            );
+            Ok(())
+        }
+        Command::Replay { log, state } => {
+            let text =
+                fs::read_to_string(&log).with_context(|| format!("Reading {}", log.display()))?;
+            let record: GameRecord = match log.extension().and_then(|e| e.to_str()) {
+                Some("json") => serde_json::from_str(&text)?,
+                _ => Notation::parse(&text)
+                    .map_err(|e| anyhow!("{}", e))?
+                    .to_record()?,
+            };
+
+            let replay = prove_record(&record, None)?;
+            write_receipt(&receipt_path(&state, "init"), replay.init.receipt())?;
+            for (i, play) in replay.plays.iter().enumerate() {
+                write_receipt(&receipt_path(&state, &(i + 1).to_string()), play.receipt())?;
+            }
+            match &replay.winners {
+                Some(winners) => {
+                    println!("{:#?}", winners.verify_and_get_commit()?);
+                    write_receipt(&receipt_path(&state, "winners"), &winners.receipt)?;
+                }
+                None => println!("Game has not ended, so there is no winners receipt"),
+            }
+            replay.game.save(&state)
+        }
+        Command::Inspect { state } => {
+            let game = load(&state)?;
+            let state = &game.state;
+            println!("Game:          {}", hex(&state.game_id.0));
+            println!("Board:         {:?}", state.board);
+            println!("End condition: {:?}", state.end_condition);
+            println!("Move number:   {}", state.move_number);
+            println!("State hash:    {}", state.digest());
+            println!("Winners:       {:?}", state.winners);
+            println!("Eliminated:    {:?}", state.eliminated);
+            println!("Moves saved:   {}", game.record.moves.len());
+            println!("Receipts:      {}", game.receipts.len());
+            print!("\n{}", render_ascii(state));
+            Ok(())
+        }
+        Command::Hotseat {
+            no_prove,
+            six_player,
+        } => {
+            let board = if six_player {
+                BoardLayout::SixPlayer
+            } else {
+                BoardLayout::Classic
+            };
+            let game = Game::new(
+                LudoGameState::new(board, players::get_players(board)).with_game_id(new_game_id()),
+            );
+            terminal::run_hot_seat(game, !no_prove)
+        }
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ludo_core::salted_digest;
+
+    #[test]
+    fn salted_exports_check_the_salted_commitment() {
+        let board = BoardLayout::Classic;
+        let state = LudoGameState::new(board, players::get_players(board));
+        let salt = [7u8; 32];
+
+        let hidden = Game::new(state.clone()).with_salt(salt);
+        assert!(ensure_saved_state(&hidden, salted_digest(&salt, &state)).is_ok());
+        assert!(ensure_saved_state(&hidden, state.digest()).is_err());
+
+        let open = Game::new(state.clone());
+        assert!(ensure_saved_state(&open, state.digest()).is_ok());
+        assert!(ensure_saved_state(&open, salted_digest(&salt, &state)).is_err());
+    }
+}
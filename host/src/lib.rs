@@ -1,21 +1,39 @@
 use bincode;
 use ludo_core::{
-    InitializeGameStateCommit, LudoGameState, Play, PlayGameCommit, PlayGameParams, WinnersCommit,
+    salted_digest, Action, Digest, DisclosureCommit, GameEvent, GameRecord,
+    InitializeGameStateCommit, LudoGameState, MoveRecord, PackedState, Play, PlayGameCommit,
+    PlayGameParams, PlayJournal, Resign, Roster, Salt, Statement, WinnersCommit, PACKED_STATE_LEN,
+};
+use methods::{
+    DISCLOSE_ELF, DISCLOSE_ID, INIT_ELF, INIT_ID, PLAY_ELF, PLAY_ID, WINNERS_ELF, WINNERS_ID,
 };
-use methods::{INIT_ELF, INIT_ID, PLAY_ELF, PLAY_ID, WINNERS_ELF, WINNERS_ID};
 use risc0_zkvm::{default_prover, serde::from_slice, ExecutorEnv, Receipt, Result};
+use save::ReceiptRef;
 use std::fs;
 
 const PROOF_FILE_PATH: &str = "play_receipt.proof";
 const IMAGE_ID_FILE_PATH: &str = "play_receipt_id.bin";
 const PUB_INPUT_FILE_PATH: &str = "play_receipt.pub";
 
+pub mod channel;
 pub mod players;
+pub mod replay;
+pub mod save;
+pub mod terminal;
+pub mod transcript;
 pub struct InitMessage {
     receipt: Receipt,
 }
 
 impl InitMessage {
+    pub fn new(receipt: Receipt) -> Self {
+        Self { receipt }
+    }
+
+    pub fn receipt(&self) -> &Receipt {
+        &self.receipt
+    }
+
     pub fn get_state(&self) -> Result<InitializeGameStateCommit> {
         Ok(self.receipt.journal.decode()?)
     }
@@ -31,24 +49,74 @@ pub struct PlayMessage {
 }
 
 impl PlayMessage {
+    pub fn new(receipt: Receipt) -> Self {
+        Self { receipt }
+    }
+
+    pub fn receipt(&self) -> &Receipt {
+        &self.receipt
+    }
+
     pub fn get_state(&self) -> Result<PlayGameCommit> {
-        Ok(self.receipt.journal.decode()?)
+        match self.get_journal()? {
+            PlayJournal::V4(commit) => Ok(commit),
+            PlayJournal::V1(_) | PlayJournal::V2(_) => {
+                Err(anyhow::anyhow!("Play journal predates the current layout"))
+            }
+            PlayJournal::V3(_) => Err(anyhow::anyhow!("Hidden play journal has no game details")),
+        }
+    }
+
+    // Decodes the journal of any supported layout, including pre-versioning receipts
+    pub fn get_journal(&self) -> Result<PlayJournal> {
+        Ok(PlayJournal::decode(&self.receipt.journal.bytes)?)
     }
 
     pub fn verify_and_get_commit(&self) -> Result<PlayGameCommit> {
         self.receipt.verify(PLAY_ID)?;
         self.get_state()
     }
+
+    pub fn verify_and_get_journal(&self) -> Result<PlayJournal> {
+        self.receipt.verify(PLAY_ID)?;
+        self.get_journal()
+    }
 }
 
 #[derive(Debug)]
 pub struct Game {
     pub state: LudoGameState,
+    pub record: GameRecord,        // Every proven action and its events
+    pub salt: Option<Salt>,        // Hides every committed state when set
+    pub receipts: Vec<ReceiptRef>, // Every receipt proven for the game, in order
 }
 
 impl Game {
     pub fn new(state: LudoGameState) -> Self {
-        Self { state }
+        Self {
+            record: GameRecord::new(state.clone()),
+            state,
+            salt: None,
+            receipts: vec![],
+        }
+    }
+
+    // Commits to salted state hashes and keeps play journals hidden
+    pub fn with_salt(mut self, salt: Salt) -> Self {
+        self.salt = Some(salt);
+        self
+    }
+
+    // What the receipts commit to for the current state
+    pub fn commitment(&self) -> Digest {
+        self.commitment_to(&self.state)
+    }
+
+    fn commitment_to(&self, state: &LudoGameState) -> Digest {
+        match &self.salt {
+            Some(salt) => salted_digest(salt, state),
+            None => state.digest(),
+        }
     }
 
     // Helper function to write receipts to files
@@ -60,41 +128,125 @@ impl Game {
         Ok(())
     }
 
-    pub fn init(&self) -> Result<InitMessage> {
-        let env = ExecutorEnv::builder().write(&self.state)?.build()?;
+    pub fn init(&mut self) -> Result<InitMessage> {
+        let env = ExecutorEnv::builder()
+            .write_slice(&PackedState::new(&self.state).to_words())
+            .write(&Roster::new(&self.state))?
+            .write(&self.salt)?
+            .build()?;
         let prover = default_prover();
         let receipt = prover.prove(env, INIT_ELF)?.receipt;
         Self::write_receipt_to_files(&receipt, &INIT_ID)?;
+        self.receipts.push(ReceiptRef::new(&receipt, INIT_ID));
         Ok(InitMessage { receipt })
     }
 
     pub fn play(&mut self, play: &Play) -> Result<PlayMessage> {
-        let params = PlayGameParams::new(self.state.clone(), play.clone());
+        self.apply(Action::Play(play.clone()))
+    }
+
+    pub fn resign(&mut self, resign: &Resign) -> Result<PlayMessage> {
+        self.apply(Action::Resign(resign.clone()))
+    }
+
+    // Applies an action natively without proving it, for local play and rule checks
+    pub fn apply_unproven(&mut self, action: Action) -> Result<&MoveRecord> {
+        let result = PlayGameParams::new(self.state.clone(), action.clone()).process()?;
+        self.state = result.state;
+        Ok(self.record.record(action, result.events))
+    }
+
+    // Proves a single action with the play guest, adopts the resulting state
+    // and records the events it produced
+    fn apply(&mut self, action: Action) -> Result<PlayMessage> {
+        // The guest would reject an illegal action anyway; fail before proving
+        PlayGameParams::new(self.state.clone(), action.clone()).validate()?;
+
+        let roster = Roster::new(&self.state);
         let mut output = Vec::new();
         let env = ExecutorEnv::builder()
-            .write(&params)?
+            .write_slice(&PackedState::new(&self.state).to_words())
+            .write(&roster)?
+            .write(&action)?
+            .write(&self.salt)?
             .stdout(&mut output)
             .build()?;
         let prover = default_prover();
         let receipt = prover.prove(env, PLAY_ELF)?.receipt;
         Self::write_receipt_to_files(&receipt, &PLAY_ID)?;
-        self.state = from_slice(&output)?;
+
+        // The guest writes the packed new state, then the events
+        if output.len() < PACKED_STATE_LEN {
+            return Err(anyhow::anyhow!("Play guest output is truncated"));
+        }
+        let (packed, events) = output.split_at(PACKED_STATE_LEN);
+        let state = PackedState(packed.try_into()?).unpack(&roster)?;
+        let events: Vec<GameEvent> = from_slice(events)?;
+
+        // The journal must commit to the state the guest handed back
+        if PlayJournal::decode(&receipt.journal.bytes)?.new_state() != self.commitment_to(&state) {
+            return Err(anyhow::anyhow!(
+                "Play journal does not commit to the new state"
+            ));
+        }
+
+        self.state = state;
+        self.record.record(action, events);
+        self.receipts.push(ReceiptRef::new(&receipt, PLAY_ID));
         Ok(PlayMessage { receipt })
     }
 
-    pub fn verify_winners(&self) -> Result<WinnersMessage> {
-        // Check if we have up to 3 winners
-        if self.state.winners.len() >= 3 {
-            return Err(anyhow::anyhow!("Game must have up to 3 winners"));
+    pub fn verify_winners(&mut self) -> Result<WinnersMessage> {
+        // Check the game has reached its end condition
+        if !self.state.is_over() {
+            return Err(anyhow::anyhow!("Game is not over yet"));
         }
 
-        let env = ExecutorEnv::builder().write(&self.state)?.build()?;
+        let env = ExecutorEnv::builder()
+            .write_slice(&PackedState::new(&self.state).to_words())
+            .write(&Roster::new(&self.state))?
+            .write(&self.salt)?
+            .build()?;
 
         let prover = default_prover();
         let receipt = prover.prove(env, WINNERS_ELF)?.receipt;
         Self::write_receipt_to_files(&receipt, &WINNERS_ID)?;
+        self.receipts.push(ReceiptRef::new(&receipt, WINNERS_ID));
         Ok(WinnersMessage { receipt })
     }
+
+    // Proves a statement about a single player against the current state root,
+    // without revealing the other players' pieces
+    pub fn disclose(&self, statement: Statement) -> Result<DisclosureMessage> {
+        if self.salt.is_some() {
+            return Err(anyhow::anyhow!(
+                "Hidden games commit to salted hashes, not state roots"
+            ));
+        }
+        let params = self.state.disclosure(statement);
+        if !params.verify() {
+            return Err(anyhow::anyhow!(
+                "Statement does not hold for the current state"
+            ));
+        }
+
+        let env = ExecutorEnv::builder().write(&params)?.build()?;
+        let prover = default_prover();
+        let receipt = prover.prove(env, DISCLOSE_ELF)?.receipt;
+        Self::write_receipt_to_files(&receipt, &DISCLOSE_ID)?;
+        Ok(DisclosureMessage { receipt })
+    }
+}
+
+pub struct DisclosureMessage {
+    pub receipt: Receipt,
+}
+
+impl DisclosureMessage {
+    pub fn verify_and_get_commit(&self) -> Result<DisclosureCommit> {
+        self.receipt.verify(DISCLOSE_ID)?;
+        Ok(self.receipt.journal.decode()?)
+    }
 }
 
 pub struct WinnersMessage {
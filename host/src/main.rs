@@ -1,73 +1,30 @@
-use host::{players, Game};
-use ludo_core::LudoGameState;
+use clap::Parser;
+
+mod cli;
+
 fn main() {
     // Initialize tracing. In order to view logs, run `RUST_LOG=info cargo run`
     tracing_subscriber::fmt()
         .with_env_filter(tracing_subscriber::filter::EnvFilter::from_default_env())
         .init();
 
-    let players = players::get_players();
-
-    let ludo_game_state = LudoGameState {
-        players,
-        current_player: 0,
-        dice_roll: 0,
-        winners: vec![],
-        sixes: 0,
-    };
-    let mut game = Game::new(ludo_game_state);
-    match game.init() {
-        Ok(init_message) => match init_message.verify_and_get_commit() {
-            Ok(commit) => println!("Commit: {:?}", commit),
-            Err(e) => eprintln!("Failed to verify init commit: {:?}", e),
-        },
-        Err(e) => eprintln!("Failed to init game: {:?}", e),
+    if let Err(e) = cli::run(cli::Cli::parse()) {
+        eprintln!("Error: {:#}", e);
+        std::process::exit(1);
     }
-
-    // let play1 = Play {
-    //     current_player: 0,
-    //     dice_roll: 6,
-    //     piece_index: 0,
-    // };
-
-    // match game.play(&play1) {
-    //     Ok(play_message) => match play_message.verify_and_get_commit() {
-    //         Ok(commit) => println!("Commit: {:?}", commit),
-    //         Err(e) => eprintln!("Failed to verify play commit: {:?}", e),
-    //     },
-    //     Err(e) => eprintln!("Failed to play game: {:?}", e),
-    // }
-
-    // let play2 = Play {
-    //     current_player: 0,
-    //     dice_roll: 2,
-    //     piece_index: 0,
-    // };
-
-    // let play_message2 = game.play(&play2).unwrap();
-    // println!("");
-    // let commit2 = play_message2.verify_and_get_commit().unwrap();
-    // println!("Commit2: {:?}", commit2);
-    // println!("");
-    // println!("Game state: {:?}", game.state);
 }
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use ludo_core::{LudoGameState, Play};
+    use host::transcript::TranscriptVerifier;
+    use host::{players, Game};
+    use ludo_core::{BoardLayout, GameId, LudoGameState, Play, Statement};
 
     #[test]
     fn test_game_initialization_and_play() {
         // Setup initial game state
-        let players = players::get_players();
-        let ludo_game_state = LudoGameState {
-            players,
-            current_player: 0,
-            dice_roll: 0,
-            winners: vec![],
-            sixes: 0,
-        };
+        let players = players::get_players(BoardLayout::Classic);
+        let ludo_game_state = LudoGameState::new(BoardLayout::Classic, players);
 
         let mut game = Game::new(ludo_game_state);
 
@@ -85,6 +42,7 @@ mod tests {
             current_player: 0,
             dice_roll: 6,
             piece_index: 0,
+            signature: None,
         };
 
         match game.play(&play1) {
@@ -100,6 +58,7 @@ mod tests {
             current_player: 0,
             dice_roll: 2,
             piece_index: 0,
+            signature: None,
         };
 
         match game.play(&play2) {
@@ -112,4 +71,66 @@ mod tests {
 
         println!("Game state: {:?}", game.state);
     }
+
+    #[test]
+    fn test_transcript_rejects_receipts_from_another_game() {
+        let new_game = |id| {
+            let players = players::get_players(BoardLayout::Classic);
+            Game::new(LudoGameState::new(BoardLayout::Classic, players).with_game_id(GameId(id)))
+        };
+        let play = Play {
+            current_player: 0,
+            dice_roll: 6,
+            piece_index: 0,
+            signature: None,
+        };
+
+        let mut game = new_game([1; 16]);
+        let mut transcript =
+            TranscriptVerifier::new(&game.init().expect("Failed to init game")).unwrap();
+
+        // Same position and move, but proven for a different game
+        let mut other_game = new_game([2; 16]);
+        let replayed = other_game.play(&play).expect("Failed to play game");
+        assert!(transcript.verify_play(&replayed).is_err());
+
+        let play_message = game.play(&play).expect("Failed to play game");
+        let journal = transcript.verify_play(&play_message).unwrap();
+        assert_eq!(journal.game_move(), Some((GameId([1; 16]), 1)));
+
+        // A receipt cannot be applied twice
+        assert!(transcript.verify_play(&play_message).is_err());
+    }
+
+    #[test]
+    fn test_disclosure_matches_transcript_state_root() {
+        let players = players::get_players(BoardLayout::Classic);
+        let mut game = Game::new(LudoGameState::new(BoardLayout::Classic, players));
+        let mut transcript =
+            TranscriptVerifier::new(&game.init().expect("Failed to init game")).unwrap();
+        let play = Play {
+            current_player: 0,
+            dice_roll: 6,
+            piece_index: 0,
+            signature: None,
+        };
+        let play_message = game.play(&play).expect("Failed to play game");
+        transcript.verify_play(&play_message).unwrap();
+
+        // Red's first piece entered on its start cell
+        let statement = Statement::PieceAtCell {
+            player: 0,
+            piece: 0,
+            cell: 19,
+        };
+        let disclosure = game
+            .disclose(statement.clone())
+            .expect("Failed to disclose");
+        let commit = transcript.verify_disclosure(&disclosure).unwrap();
+        assert_eq!(commit.statement, statement);
+
+        assert!(game
+            .disclose(Statement::AllPiecesWon { player: 0 })
+            .is_err());
+    }
 }
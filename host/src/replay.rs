@@ -0,0 +1,120 @@
+use crate::transcript::TranscriptVerifier;
+use crate::{Game, InitMessage, PlayMessage, WinnersMessage};
+use anyhow::{anyhow, ensure, Result};
+use ludo_core::{Action, GameRecord, PlayGameParams, Salt};
+use risc0_zkvm::Receipt;
+
+// A recorded game proven again from its initial state
+pub struct ProvenReplay {
+    pub game: Game,
+    pub init: InitMessage,
+    pub plays: Vec<PlayMessage>,
+    pub winners: Option<WinnersMessage>, // Only once the game has ended
+}
+
+impl ProvenReplay {
+    // Every receipt in transcript order: init, each action, then the winners
+    pub fn receipts(&self) -> Vec<Receipt> {
+        let mut receipts = vec![self.init.receipt().clone()];
+        receipts.extend(self.plays.iter().map(|play| play.receipt().clone()));
+        receipts.extend(self.winners.iter().map(|winners| winners.receipt.clone()));
+        receipts
+    }
+}
+
+// Checks a game log replays under the rules, without proving anything. Moves
+// that were recorded with their events must produce the same events again.
+pub fn check_record(record: &GameRecord) -> Result<()> {
+    let mut state = record.initial_state.clone();
+    for entry in &record.moves {
+        let result = PlayGameParams::new(state, entry.action.clone())
+            .process()
+            .map_err(|e| anyhow!("Move {} is not legal: {}", entry.move_number, e))?;
+        ensure!(
+            entry.events.is_empty() || entry.events == result.events,
+            "Move {} does not produce the recorded events",
+            entry.move_number
+        );
+        state = result.state;
+    }
+    Ok(())
+}
+
+// Proves every action of a recorded game, then the winners once it has ended,
+// and checks the receipts form one transcript. Pass the game's salt to
+// reproduce the commitments of a hidden game.
+pub fn prove_record(record: &GameRecord, salt: Option<Salt>) -> Result<ProvenReplay> {
+    check_record(record)?;
+
+    let mut game = Game::new(record.initial_state.clone());
+    if let Some(salt) = salt {
+        game = game.with_salt(salt);
+    }
+    let init = game.init()?;
+    let mut transcript = TranscriptVerifier::new(&init)?;
+    let mut plays = vec![];
+    for entry in &record.moves {
+        let play = match &entry.action {
+            Action::Play(play) => game.play(play)?,
+            Action::Resign(resign) => game.resign(resign)?,
+        };
+        transcript.verify_play(&play)?;
+        plays.push(play);
+    }
+    let winners = match game.state.is_over() {
+        true => {
+            let winners = game.verify_winners()?;
+            transcript.verify_winners(&winners)?;
+            Some(winners)
+        }
+        false => None,
+    };
+    Ok(ProvenReplay {
+        game,
+        init,
+        plays,
+        winners,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players;
+    use ludo_core::{BoardLayout, LudoGameState, Play};
+
+    #[test]
+    fn logs_that_break_the_rules_are_rejected() {
+        let board = BoardLayout::Classic;
+        let mut game = Game::new(LudoGameState::new(board, players::get_players(board)));
+        let play = |dice_roll, piece_index| {
+            Action::Play(Play {
+                current_player: 0,
+                dice_roll,
+                piece_index,
+                signature: None,
+            })
+        };
+        game.apply_unproven(play(6, 0)).unwrap();
+        game.apply_unproven(play(4, 0)).unwrap();
+        assert!(check_record(&game.record).is_ok());
+
+        // Events that the move did not produce
+        let mut record = game.record.clone();
+        record.moves[1].events = record.moves[0].events.clone();
+        assert!(check_record(&record).is_err());
+
+        // Logs without events are replayed as they are
+        record.moves.iter_mut().for_each(|m| m.events.clear());
+        assert!(check_record(&record).is_ok());
+
+        // A piece still in the yard cannot move on a four
+        record.moves[1].action = play(4, 1);
+        assert!(check_record(&record).is_err());
+
+        // Players with a key must have signed their moves
+        let mut record = game.record.clone();
+        record.initial_state.players[0].public_key = Some([1; 32]);
+        assert!(check_record(&record).is_err());
+    }
+}
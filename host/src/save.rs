@@ -0,0 +1,259 @@
+use crate::Game;
+use anyhow::{anyhow, Result};
+use ludo_core::{digest::sha256, Digest, GameRecord, LudoGameState, Salt};
+use risc0_zkvm::Receipt;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// Identifies a proven receipt without storing it: the guest that proved it and
+// the hash of its journal. Receipts themselves are kept wherever the caller
+// wrote them.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ReceiptRef {
+    pub image_id: [u32; 8],
+    pub journal: Digest,
+}
+
+impl ReceiptRef {
+    pub fn new(receipt: &Receipt, image_id: [u32; 8]) -> Self {
+        ReceiptRef {
+            image_id,
+            journal: sha256(&receipt.journal.bytes),
+        }
+    }
+
+    pub fn matches(&self, receipt: &Receipt) -> bool {
+        sha256(&receipt.journal.bytes) == self.journal && receipt.verify(self.image_id).is_ok()
+    }
+}
+
+// Bump whenever the layout of `SaveFile` or any type inside it changes, and
+// teach `migrate` to upgrade the previous version
+pub const SAVE_VERSION: u32 = 1;
+
+// Heads every bincode save, followed by the version as a little-endian u32
+const BINARY_MAGIC: &[u8; 4] = b"LSAV";
+
+// Everything needed to pick a game up again. The salt is not part of it: it
+// would reveal every hidden state, so it is kept in its own file, see `salt_path`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32, // Always SAVE_VERSION when written
+    pub state: LudoGameState,
+    pub salted: bool,       // The game hides its states and needs its salt file
+    pub record: GameRecord, // Move history from the initial state
+    pub receipts: Vec<ReceiptRef>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SaveFormat {
+    Json,   // Readable, for people and tools
+    Binary, // Bincode behind a magic and version header, for compact storage
+}
+
+impl SaveFormat {
+    // `.json` files are JSON, anything else binary
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => SaveFormat::Json,
+            _ => SaveFormat::Binary,
+        }
+    }
+}
+
+// Just the version, read before the rest of a JSON file
+#[derive(Deserialize)]
+struct SaveVersion {
+    version: u32,
+}
+
+fn decode<T: DeserializeOwned>(bytes: &[u8], format: SaveFormat) -> Result<T> {
+    Ok(match format {
+        SaveFormat::Json => serde_json::from_slice(bytes)?,
+        SaveFormat::Binary => bincode::deserialize(bytes)?,
+    })
+}
+
+// Splits a bincode save into its version and body
+fn binary_version(bytes: &[u8]) -> Result<(u32, &[u8])> {
+    let header = bytes
+        .strip_prefix(BINARY_MAGIC)
+        .filter(|rest| rest.len() >= 4)
+        .ok_or_else(|| anyhow!("Not a binary save file"))?;
+    let (version, body) = header.split_at(4);
+    Ok((u32::from_le_bytes(version.try_into()?), body))
+}
+
+impl SaveFile {
+    pub fn new(game: &Game) -> Self {
+        SaveFile {
+            version: SAVE_VERSION,
+            state: game.state.clone(),
+            salted: game.salt.is_some(),
+            record: game.record.clone(),
+            receipts: game.receipts.clone(),
+        }
+    }
+
+    pub fn encode(&self, format: SaveFormat) -> Result<Vec<u8>> {
+        Ok(match format {
+            SaveFormat::Json => serde_json::to_vec_pretty(self)?,
+            SaveFormat::Binary => {
+                let mut bytes = BINARY_MAGIC.to_vec();
+                bytes.extend_from_slice(&self.version.to_le_bytes());
+                bytes.extend(bincode::serialize(self)?);
+                bytes
+            }
+        })
+    }
+
+    pub fn decode(bytes: &[u8], format: SaveFormat) -> Result<Self> {
+        let (version, body) = match format {
+            SaveFormat::Json => (decode::<SaveVersion>(bytes, format)?.version, bytes),
+            SaveFormat::Binary => binary_version(bytes)?,
+        };
+        let save = match version {
+            SAVE_VERSION => decode::<SaveFile>(body, format)?,
+            _ => migrate(version, body, format)?,
+        };
+        if save.version != SAVE_VERSION {
+            return Err(anyhow!(
+                "Save header says version {} but the body is version {}",
+                version,
+                save.version
+            ));
+        }
+        Ok(save)
+    }
+
+    // `salt` comes from the salt file, and is only needed for salted games
+    pub fn into_game(self, salt: Option<Salt>) -> Result<Game> {
+        if self.salted != salt.is_some() {
+            return Err(anyhow!(match self.salted {
+                true => "Salted game is missing its salt",
+                false => "Salt given for a game that is not salted",
+            }));
+        }
+        Ok(Game {
+            state: self.state,
+            record: self.record,
+            salt,
+            receipts: self.receipts,
+        })
+    }
+}
+
+// Upgrades a save written under an older version to the current layout. Keep
+// the layout of every old version here, frozen, and convert it step by step.
+// There is no older version yet.
+fn migrate(version: u32, _bytes: &[u8], _format: SaveFormat) -> Result<SaveFile> {
+    Err(anyhow!("Unsupported save version {}", version))
+}
+
+// Salt file next to the save, e.g. `game.salt` for `game.json`
+pub fn salt_path(path: &Path) -> PathBuf {
+    path.with_extension("salt")
+}
+
+// Only the owner may read the salt, as it unlocks every hidden state
+fn write_salt(path: &Path, salt: &Salt) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(salt)?;
+    Ok(())
+}
+
+fn read_salt(path: &Path) -> Result<Salt> {
+    fs::read(path)?
+        .try_into()
+        .map_err(|_| anyhow!("{} is not a salt", path.display()))
+}
+
+impl Game {
+    // Writes the game as JSON for `.json` paths and bincode otherwise, and the
+    // salt of a salted game to its own file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(salt) = &self.salt {
+            write_salt(&salt_path(path), salt)?;
+        }
+        fs::write(
+            path,
+            SaveFile::new(self).encode(SaveFormat::from_path(path))?,
+        )?;
+        Ok(())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Game> {
+        let path = path.as_ref();
+        let save = SaveFile::decode(&fs::read(path)?, SaveFormat::from_path(path))?;
+        let salt = match save.salted {
+            true => Some(read_salt(&salt_path(path))?),
+            false => None,
+        };
+        save.into_game(salt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::players;
+    use ludo_core::{Action, BoardLayout, Play};
+
+    #[test]
+    fn saves_round_trip_and_refuse_unknown_files() {
+        let board = BoardLayout::Classic;
+        let mut game =
+            Game::new(LudoGameState::new(board, players::get_players(board))).with_salt([3; 32]);
+        game.apply_unproven(Action::Play(Play {
+            current_player: 0,
+            dice_roll: 6,
+            piece_index: 2,
+            signature: None,
+        }))
+        .unwrap();
+        game.receipts.push(ReceiptRef {
+            image_id: [1; 8],
+            journal: Digest::new([2; 8]),
+        });
+
+        let save = SaveFile::new(&game);
+        for format in [SaveFormat::Json, SaveFormat::Binary] {
+            let bytes = save.encode(format).unwrap();
+            assert_eq!(SaveFile::decode(&bytes, format).unwrap(), save);
+        }
+        assert!(save.clone().into_game(None).is_err());
+
+        let dir = std::env::temp_dir().join(format!("ludo-save-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["game.json", "game.sav"] {
+            game.save(dir.join(name)).unwrap();
+            let loaded = Game::load(dir.join(name)).unwrap();
+            assert_eq!(loaded.salt, game.salt);
+            assert_eq!(SaveFile::new(&loaded), save);
+        }
+        assert_eq!(fs::read(dir.join("game.salt")).unwrap(), [3; 32]);
+        fs::remove_dir_all(dir).unwrap();
+
+        // Files without the header or with an unknown version are refused
+        let bytes = save.encode(SaveFormat::Binary).unwrap();
+        assert!(SaveFile::decode(&bytes[4..], SaveFormat::Binary).is_err());
+        assert!(SaveFile::decode(
+            &bincode::serialize(&game.state).unwrap(),
+            SaveFormat::Binary
+        )
+        .is_err());
+        let mut future = bytes;
+        future[4] = 9;
+        assert!(SaveFile::decode(&future, SaveFormat::Binary).is_err());
+        let mut json = save;
+        json.version = 9;
+        let json = json.encode(SaveFormat::Json).unwrap();
+        assert!(SaveFile::decode(&json, SaveFormat::Json).is_err());
+    }
+}
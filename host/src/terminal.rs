@@ -0,0 +1,151 @@
+use crate::Game;
+use anyhow::Result;
+use ludo_core::{render_ascii, Action, GameEvent, LudoGameState, Play, PlayJournal, Resign};
+use rand::Rng;
+use std::io::{self, BufRead, Write};
+
+fn prompt(input: &mut impl BufRead, message: &str) -> Result<Option<String>> {
+    print!("{}", message);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
+
+fn describe(event: &GameEvent, state: &LudoGameState) -> String {
+    let color = |player: usize| format!("{:?}", state.players[player].color);
+    match event {
+        GameEvent::DiceRolled { player, value } => format!("{} rolled {}", color(*player), value),
+        GameEvent::PieceEntered { piece } => {
+            format!(
+                "{} piece {} entered the board",
+                color(piece.player),
+                piece.piece
+            )
+        }
+        GameEvent::PieceMoved { piece, from, to } => format!(
+            "{} piece {} moved from {} to {}",
+            color(piece.player),
+            piece.piece,
+            from,
+            to
+        ),
+        GameEvent::PieceCaptured { by, victim } => format!(
+            "{} piece {} captured {} piece {}",
+            color(by.player),
+            by.piece,
+            color(victim.player),
+            victim.piece
+        ),
+        GameEvent::PieceFinished { piece } => {
+            format!("{} piece {} reached home", color(piece.player), piece.piece)
+        }
+        GameEvent::PlayerWon { player } => format!("{} finished", color(*player)),
+        GameEvent::PlayerEliminated { player } => format!("{} left the game", color(*player)),
+        GameEvent::TurnPassed {
+            next_player,
+            reason,
+            ..
+        } => format!("{:?}, {} to play", reason, color(*next_player)),
+    }
+}
+
+// Plays a game at one terminal, every player taking turns at the keyboard.
+// Each action is proven unless `prove` is off, which makes for quick rule checks.
+pub fn run_hot_seat(mut game: Game, prove: bool) -> Result<()> {
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    if prove {
+        let commit = game.init()?.verify_and_get_commit()?;
+        println!("Init commit: {:?}\n", commit);
+    }
+
+    while !game.state.is_over() {
+        print!("\n{}", render_ascii(&game.state));
+        let player = game.state.current_player;
+        let color = format!("{:?}", game.state.players[player].color);
+
+        let message = format!(
+            "{} to play. Enter to roll, 1-6 to set the dice, r to resign, q to quit: ",
+            color
+        );
+        let Some(answer) = prompt(&mut input, &message)? else {
+            return Ok(());
+        };
+        let action = match answer.as_str() {
+            "q" => return Ok(()),
+            "r" => Action::Resign(Resign {
+                player,
+                signature: None,
+            }),
+            "" => roll(&game.state, rand::thread_rng().gen_range(1..=6), &mut input)?,
+            value => match value.parse::<u8>() {
+                Ok(dice_roll @ 1..=6) => roll(&game.state, dice_roll, &mut input)?,
+                _ => {
+                    println!("Not a dice value: {}", value);
+                    continue;
+                }
+            },
+        };
+
+        let before = game.state.clone();
+        let events = if prove {
+            let message = match &action {
+                Action::Play(play) => game.play(play)?,
+                Action::Resign(resign) => game.resign(resign)?,
+            };
+            let journal: PlayJournal = message.verify_and_get_journal()?;
+            println!("Play commit: {:?}", journal);
+            game.record.moves.last().unwrap().events.clone()
+        } else {
+            game.apply_unproven(action)?.events.clone()
+        };
+        for event in &events {
+            println!("  {}", describe(event, &before));
+        }
+    }
+
+    print!("\n{}", render_ascii(&game.state));
+    for placement in game.state.placements() {
+        println!(
+            "{}. {:?}",
+            placement.rank, game.state.players[placement.player].color
+        );
+    }
+    if prove {
+        let commit = game.verify_winners()?.verify_and_get_commit()?;
+        println!("Winners commit: {:?}", commit);
+    }
+    Ok(())
+}
+
+// Builds the play for a roll, asking which piece to move when there is a choice
+fn roll(state: &LudoGameState, dice_roll: u8, input: &mut impl BufRead) -> Result<Action> {
+    let legal_moves = state.legal_moves(dice_roll);
+    println!("Rolled {}", dice_roll);
+    let piece_index = match legal_moves.as_slice() {
+        [] => {
+            println!("No piece can move");
+            0
+        }
+        [only] => *only,
+        _ => loop {
+            let message = format!("Piece to move {:?}: ", legal_moves);
+            let Some(answer) = prompt(input, &message)? else {
+                return Err(anyhow::anyhow!("Input closed"));
+            };
+            match answer.parse::<u8>() {
+                Ok(piece) if legal_moves.contains(&piece) => break piece,
+                _ => println!("Pick one of {:?}", legal_moves),
+            }
+        },
+    };
+    Ok(Action::Play(Play {
+        current_player: state.current_player,
+        dice_roll,
+        piece_index,
+        signature: None,
+    }))
+}
@@ -0,0 +1,106 @@
+use crate::{DisclosureMessage, InitMessage, PlayMessage, WinnersMessage};
+use anyhow::{anyhow, ensure};
+use ludo_core::{
+    salted_digest, Digest, DisclosureCommit, GameId, LudoGameState, PlayJournal, Salt,
+    WinnersCommit,
+};
+use risc0_zkvm::Result;
+
+// Checks that a sequence of receipts forms one unbroken game: every receipt
+// must carry the game id from the init receipt, continue from the previous
+// state hash and advance the move number by exactly one. This rejects
+// receipts replayed from another game, even one with an identical position.
+#[derive(Debug)]
+pub struct TranscriptVerifier {
+    game_id: GameId,
+    state_hash: Digest,
+    move_number: u32,
+    finished: bool,
+}
+
+impl TranscriptVerifier {
+    pub fn new(init: &InitMessage) -> Result<Self> {
+        let commit = init.verify_and_get_commit()?;
+        Ok(Self {
+            game_id: commit.game_id,
+            state_hash: commit.state_hash,
+            move_number: 0,
+            finished: false,
+        })
+    }
+
+    pub fn game_id(&self) -> GameId {
+        self.game_id
+    }
+
+    // Hash of the latest state reached by the transcript
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    pub fn move_number(&self) -> u32 {
+        self.move_number
+    }
+
+    // Accepts both public and hidden play journals
+    pub fn verify_play(&mut self, play: &PlayMessage) -> Result<PlayJournal> {
+        ensure!(!self.finished, "Game already has a winners receipt");
+        let journal = play.verify_and_get_journal()?;
+        let (game_id, move_number) = journal
+            .game_move()
+            .ok_or_else(|| anyhow!("Legacy play receipt is not bound to a game"))?;
+        self.check_game(game_id, move_number, self.move_number + 1)?;
+        ensure!(
+            journal.old_state() == self.state_hash,
+            "Play receipt does not continue from the previous state"
+        );
+
+        self.state_hash = journal.new_state();
+        self.move_number = move_number;
+        Ok(journal)
+    }
+
+    pub fn verify_winners(&mut self, winners: &WinnersMessage) -> Result<WinnersCommit> {
+        ensure!(!self.finished, "Game already has a winners receipt");
+        let commit = winners.verify_and_get_commit()?;
+        self.check_game(commit.game_id, commit.move_number, self.move_number)?;
+        ensure!(
+            commit.state_hash == self.state_hash,
+            "Winners receipt is not for the final state"
+        );
+
+        self.finished = true;
+        Ok(commit)
+    }
+
+    // Accepts a disclosure only if it was proven against the latest state root
+    pub fn verify_disclosure(&self, disclosure: &DisclosureMessage) -> Result<DisclosureCommit> {
+        let commit = disclosure.verify_and_get_commit()?;
+        ensure!(
+            commit.state_root == self.state_hash,
+            "Disclosure is not for the latest state"
+        );
+        Ok(commit)
+    }
+
+    // Settles a dispute over a hidden game: checks that a revealed state and
+    // salt open the latest commitment in the transcript
+    pub fn check_revealed_state(&self, state: &LudoGameState, salt: &Salt) -> Result<()> {
+        ensure!(
+            salted_digest(salt, state) == self.state_hash,
+            "Revealed state does not match the committed state"
+        );
+        Ok(())
+    }
+
+    fn check_game(&self, game_id: GameId, move_number: u32, expected: u32) -> Result<()> {
+        ensure!(game_id == self.game_id, "Receipt belongs to another game");
+        ensure!(
+            move_number == expected,
+            "Expected move {}, receipt is for move {}",
+            expected,
+            move_number
+        );
+        Ok(())
+    }
+}
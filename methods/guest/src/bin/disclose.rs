@@ -0,0 +1,22 @@
+#![no_main]
+#![no_std]
+
+use ludo_core::{DisclosureCommit, DisclosureParams};
+use risc0_zkvm::guest::env;
+
+risc0_zkvm::guest::entry!(main);
+
+fn main() {
+    // Only the disclosed player's leaf and its Merkle path are read, the rest
+    // of the state stays with the host
+    let params: DisclosureParams = env::read();
+    assert!(
+        params.verify(),
+        "Statement does not hold for the committed state"
+    );
+
+    env::commit(&DisclosureCommit {
+        state_root: params.state_root,
+        statement: params.statement,
+    });
+}
@@ -1,22 +1,32 @@
 #![no_main]
 #![no_std]
 
-use ludo_core::{InitializeGameStateCommit, LudoGameState};
-use risc0_zkvm::{
-    guest::env,
-    serde::to_vec,
-    sha::{Impl, Sha256},
+use ludo_core::{
+    salted_digest, InitializeGameStateCommit, PackedState, Roster, Salt, PACKED_STATE_WORDS,
 };
+use risc0_zkvm::guest::env;
 
 risc0_zkvm::guest::entry!(main);
 
 fn main() {
-    let state: LudoGameState = env::read();
+    let mut words = [0u32; PACKED_STATE_WORDS];
+    env::read_slice(&mut words);
+    let roster: Roster = env::read();
+    let state = PackedState::from_words(&words)
+        .unpack(&roster)
+        .expect("Packed state is malformed");
+    let salt: Option<Salt> = env::read();
     env::commit(&InitializeGameStateCommit {
+        game_id: state.game_id,
+        salted: salt.is_some(),
+        board: state.board,
         current_player: state.current_player,
         dice_roll: state.dice_roll,
         winners: state.winners.clone(),
-        sixes: state.sixes.clone(),
-        state_hash: *Impl::hash_words(&to_vec(&state).unwrap()),
+        sixes: state.sixes,
+        state_hash: match salt {
+            Some(salt) => salted_digest(&salt, &state),
+            None => state.digest(),
+        },
     });
 }
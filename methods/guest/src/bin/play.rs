@@ -1,24 +1,42 @@
 #![no_main]
 #![no_std]
 
-use ludo_core::{PlayGameCommit, PlayGameParams};
-use risc0_zkvm::{
-    guest::env,
-    serde::to_vec,
-    sha::{Impl, Sha256},
+use ludo_core::{
+    salted_digest, Action, HiddenPlayCommit, PackedState, PlayGameCommit, PlayGameParams, Roster,
+    Salt, PACKED_STATE_WORDS,
 };
+use risc0_zkvm::guest::env;
 
 risc0_zkvm::guest::entry!(main);
 
 fn main() {
-    let params: PlayGameParams = env::read();
-    let result = params.process();
-    env::write(&result.state);
-    env::commit(&PlayGameCommit {
-        old_state: *Impl::hash_words(&to_vec(&params.state).unwrap()),
-        new_state: *Impl::hash_words(&to_vec(&result.state).unwrap()),
-        current_player: params.play.current_player,
-        dice_roll: params.play.dice_roll,
-        piece_index: params.play.piece_index,
-    });
+    // The state arrives packed; the roster is only needed to rebuild it
+    let mut words = [0u32; PACKED_STATE_WORDS];
+    env::read_slice(&mut words);
+    let roster: Roster = env::read();
+    let action: Action = env::read();
+    let salt: Option<Salt> = env::read();
+    let state = PackedState::from_words(&words)
+        .unpack(&roster)
+        .expect("Packed state is malformed");
+    let params = PlayGameParams::new(state, action);
+    let result = params.process().expect("Action breaks the rules");
+
+    // Hand the new state back packed, followed by the events
+    env::write_slice(&PackedState::new(&result.state).to_words());
+    env::write(&result.events);
+    match salt {
+        // Hidden games only reveal the salted commitments
+        Some(salt) => env::commit(&HiddenPlayCommit::new(
+            &result,
+            salted_digest(&salt, &params.state),
+            salted_digest(&salt, &result.state),
+        )),
+        None => env::commit(&PlayGameCommit::new(
+            &params,
+            &result,
+            params.state.digest(),
+            result.state.digest(),
+        )),
+    }
 }
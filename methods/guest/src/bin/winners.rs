@@ -1,21 +1,35 @@
 #![no_main]
 #![no_std]
 
-use ludo_core::{LudoGameState, WinnersCommit};
+use ludo_core::{salted_digest, PackedState, Roster, Salt, WinnersCommit, PACKED_STATE_WORDS};
 use risc0_zkvm::guest::env;
 
 risc0_zkvm::guest::entry!(main);
 
 pub fn main() {
     // Read the game state from the host
-    let state: LudoGameState = env::read();
+    let mut words = [0u32; PACKED_STATE_WORDS];
+    env::read_slice(&mut words);
+    let roster: Roster = env::read();
+    let state = PackedState::from_words(&words)
+        .unpack(&roster)
+        .expect("Packed state is malformed");
+    let salt: Option<Salt> = env::read();
 
-    // Verify we have exactly 3 winners
-    assert_eq!(state.winners.len(), 3, "Game must have exactly 3 winners");
+    // Verify the game has reached its end condition
+    assert!(state.is_over(), "Game is not over yet");
 
-    // Create winners commit
+    // Create winners commit, ranking every player for prize splitting
     let winners_commit = WinnersCommit {
+        game_id: state.game_id,
+        move_number: state.move_number,
+        state_hash: match salt {
+            Some(salt) => salted_digest(&salt, &state),
+            None => state.digest(),
+        },
+        end_condition: state.end_condition,
         winners: state.winners.clone(),
+        placements: state.placements(),
     };
 
     // Commit the winners
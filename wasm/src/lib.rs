@@ -0,0 +1,111 @@
+use ludo_core::{
+    render_svg, Action, BoardGeometry, BoardLayout, LudoGameState, Piece, PieceStatus,
+    PlayGameParams, PlayJournal, Player,
+};
+use wasm_bindgen::prelude::*;
+
+// Bindings for browsers and Node. Structured values cross the boundary as the
+// same JSON the API speaks, so a front end can reuse its types.
+
+fn js_error(error: impl std::fmt::Display) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+#[wasm_bindgen]
+pub struct GameState {
+    state: LudoGameState,
+}
+
+#[wasm_bindgen]
+impl GameState {
+    // A new game on "Classic" or "SixPlayer", one player per color in board order
+    #[wasm_bindgen(constructor)]
+    pub fn new(board: &str, player_count: usize) -> Result<GameState, JsError> {
+        let board: BoardLayout =
+            serde_json::from_value(board.into()).map_err(|_| js_error("Unknown board layout"))?;
+        let colors = board.colors();
+        if player_count < 2 || player_count > board.max_players() {
+            return Err(js_error("Unsupported number of players"));
+        }
+        let players = colors[..player_count]
+            .iter()
+            .enumerate()
+            .map(|(i, color)| {
+                Player::new(
+                    format!("Player {}", i + 1),
+                    *color,
+                    [0; 4].map(|_| Piece::new(-1, PieceStatus::Home)),
+                )
+            })
+            .collect();
+        Ok(GameState {
+            state: LudoGameState::new(board, players),
+        })
+    }
+
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<GameState, JsError> {
+        let state = serde_json::from_str(json).map_err(js_error)?;
+        Ok(GameState { state })
+    }
+
+    #[wasm_bindgen(js_name = toJson)]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.state).unwrap()
+    }
+
+    #[wasm_bindgen(getter, js_name = currentPlayer)]
+    pub fn current_player(&self) -> usize {
+        self.state.current_player
+    }
+
+    #[wasm_bindgen(getter, js_name = moveNumber)]
+    pub fn move_number(&self) -> u32 {
+        self.state.move_number
+    }
+
+    #[wasm_bindgen(js_name = isOver)]
+    pub fn is_over(&self) -> bool {
+        self.state.is_over()
+    }
+
+    // Pieces the player to move could move with `dice_roll`
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&self, dice_roll: u8) -> Vec<u8> {
+        self.state.legal_moves(dice_roll)
+    }
+
+    // Applies a JSON `Action`, adopts the new state and returns the events as JSON
+    pub fn process(&mut self, action: &str) -> Result<String, JsError> {
+        let action: Action = serde_json::from_str(action).map_err(js_error)?;
+        let result = PlayGameParams::new(self.state.clone(), action)
+            .process()
+            .map_err(js_error)?;
+        self.state = result.state;
+        Ok(serde_json::to_string(&result.events).unwrap())
+    }
+
+    // Hex of the canonical state digest the guests commit to
+    pub fn digest(&self) -> String {
+        self.state.digest().to_string()
+    }
+
+    // The board and pieces as an SVG document
+    #[wasm_bindgen(js_name = toSvg)]
+    pub fn to_svg(&self) -> String {
+        render_svg(&self.state)
+    }
+}
+
+// Grid square of every classic cell id, yard and centre square, as JSON
+#[wasm_bindgen(js_name = boardGeometry)]
+pub fn board_geometry() -> String {
+    serde_json::to_string(&BoardGeometry::classic()).unwrap()
+}
+
+// Decodes the journal of a play receipt, of any supported layout, to JSON
+#[wasm_bindgen(js_name = decodePlayJournal)]
+pub fn decode_play_journal(bytes: &[u8]) -> Result<String, JsError> {
+    let journal = PlayJournal::decode(bytes).map_err(js_error)?;
+    Ok(serde_json::to_string(&journal).unwrap())
+}
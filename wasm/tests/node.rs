@@ -0,0 +1,75 @@
+// Run headless in Node with `cargo test` from this directory, which needs the
+// wasm32-unknown-unknown target and `wasm-bindgen-cli` installed
+use ludo_core::{Action, LudoGameState, Play};
+use ludo_wasm::{board_geometry, decode_play_journal, GameState};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn play(current_player: usize, dice_roll: u8, piece_index: u8) -> String {
+    let action = Action::Play(Play {
+        current_player,
+        dice_roll,
+        piece_index,
+        signature: None,
+    });
+    serde_json::to_string(&action).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn plays_a_turn_and_tracks_the_digest() {
+    let mut game = GameState::new("Classic", 4).unwrap();
+    let initial = game.digest();
+    let json: LudoGameState = serde_json::from_str(&game.to_json()).unwrap();
+    assert_eq!(initial, json.digest().to_string());
+
+    // Nothing can leave home without a six
+    assert!(game.legal_moves(3).is_empty());
+    assert_eq!(game.legal_moves(6), vec![0, 1, 2, 3]);
+
+    // Illegal moves are refused instead of aborting the module
+    assert!(game.process(&play(1, 6, 1)).is_err());
+    assert!(game.process(&play(0, 6, 4)).is_err());
+    assert_eq!(game.move_number(), 0);
+
+    let events = game.process(&play(0, 6, 1)).unwrap();
+    assert!(events.contains("PieceEntered"));
+    assert_eq!(game.move_number(), 1);
+    assert_ne!(game.digest(), initial);
+
+    let restored = GameState::from_json(&game.to_json()).unwrap();
+    assert_eq!(restored.digest(), game.digest());
+}
+
+#[wasm_bindgen_test]
+fn rejects_unknown_boards() {
+    assert!(GameState::new("Hexagon", 4).is_err());
+    assert!(GameState::new("Classic", 5).is_err());
+}
+
+#[wasm_bindgen_test]
+fn exports_the_board_geometry() {
+    let geometry: serde_json::Value = serde_json::from_str(&board_geometry()).unwrap();
+    assert_eq!(geometry["cells"].as_array().unwrap().len(), 72);
+    assert_eq!(
+        geometry["cells"][19],
+        serde_json::json!({"row": 6, "col": 1})
+    );
+    assert!(GameState::new("Classic", 4)
+        .unwrap()
+        .to_svg()
+        .starts_with("<svg "));
+}
+
+#[wasm_bindgen_test]
+fn decodes_play_journals() {
+    // A journal from before versioning: both digests, then the current
+    // player over two words, the dice roll and the piece
+    let mut words = vec![1u32; 8];
+    words.extend([2; 8]);
+    words.extend([0, 0, 6, 0]);
+    let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+
+    let decoded = decode_play_journal(&bytes).unwrap();
+    assert!(decoded.starts_with("{\"V1\""));
+    assert!(decoded.contains("\"dice_roll\":6"));
+    assert!(decode_play_journal(&[1, 2, 3]).is_err());
+}